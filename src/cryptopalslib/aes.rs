@@ -0,0 +1,316 @@
+use error::Error;
+use openssl::crypto::symm;
+
+/// Encrypts a single 16-byte block with AES-128, without any padding.
+///
+/// # Panics
+///
+/// Panics when the block isn't exactly 16 bytes long.
+///
+/// # Examples
+///
+/// ```
+/// let block = cryptopalslib::aes::encrypt_block(b"YELLOW SUBMARINE", &[0; 16]);
+/// assert_eq!(block.len(), 16);
+/// ```
+pub fn encrypt_block(key: &[u8], block: &[u8]) -> Vec<u8> {
+    if block.len() != 16 {
+        panic!("AES blocks must be 16 bytes long.");
+    }
+
+    let crypter = symm::Crypter::new(symm::Type::AES_128_ECB);
+    crypter.pad(false);
+    crypter.init(symm::Mode::Encrypt, key, vec!());
+    let mut output = crypter.update(block);
+    output.extend(crypter.finalize());
+    output
+}
+
+/// Decrypts a single 16-byte block with AES-128, without any padding.
+///
+/// # Panics
+///
+/// Panics when the block isn't exactly 16 bytes long.
+///
+/// # Examples
+///
+/// ```
+/// let key = b"YELLOW SUBMARINE";
+/// let block = cryptopalslib::aes::encrypt_block(key, b"sixteen byte msg");
+/// assert_eq!(cryptopalslib::aes::decrypt_block(key, &block), b"sixteen byte msg");
+/// ```
+pub fn decrypt_block(key: &[u8], block: &[u8]) -> Vec<u8> {
+    if block.len() != 16 {
+        panic!("AES blocks must be 16 bytes long.");
+    }
+
+    let crypter = symm::Crypter::new(symm::Type::AES_128_ECB);
+    crypter.pad(false);
+    crypter.init(symm::Mode::Decrypt, key, vec!());
+    let mut output = crypter.update(block);
+    output.extend(crypter.finalize());
+    output
+}
+
+/// Pads data to a multiple of `block_size` bytes with PKCS#7 padding.
+///
+/// # Examples
+///
+/// ```
+/// let output = cryptopalslib::aes::pkcs7_pad(b"YELLOW SUBMARINE", 20);
+/// assert_eq!(output, b"YELLOW SUBMARINE\x04\x04\x04\x04");
+/// ```
+pub fn pkcs7_pad(data: &[u8], block_size: usize) -> Vec<u8> {
+    let padding = block_size - data.len() % block_size;
+    let mut output = data.to_vec();
+    output.extend(vec!(padding as u8; padding));
+    output
+}
+
+/// Strips PKCS#7 padding from data.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::error::Error;
+///
+/// let output = cryptopalslib::aes::pkcs7_unpad(b"ICE ICE BABY\x04\x04\x04\x04");
+/// assert_eq!(output, Ok(b"ICE ICE BABY".to_vec()));
+/// let output = cryptopalslib::aes::pkcs7_unpad(b"ICE ICE BABY\x01\x02\x03\x04");
+/// assert_eq!(output, Err(Error::InvalidPadding));
+/// ```
+pub fn pkcs7_unpad(data: &[u8]) -> Result<Vec<u8>, Error> {
+    let padding = match data.last() {
+        Some(&x) => x as usize,
+        None => return Err(Error::InvalidPadding),
+    };
+    if padding == 0 || padding > data.len() {
+        return Err(Error::InvalidPadding);
+    }
+    let length = data.len() - padding;
+    if data[length..].iter().any(|&x| x as usize != padding) {
+        return Err(Error::InvalidPadding);
+    }
+    Ok(data[..length].to_vec())
+}
+
+/// Encrypts data with AES-128 in CBC mode, adding PKCS#7 padding.
+///
+/// # Examples
+///
+/// ```
+/// let key = b"YELLOW SUBMARINE";
+/// let ciphertext = cryptopalslib::aes::cbc_encrypt(key, &[0; 16], b"attack at dawn");
+/// assert_eq!(ciphertext.len(), 16);
+/// ```
+pub fn cbc_encrypt(key: &[u8], iv: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let mut previous = iv.to_vec();
+    let mut output = vec!();
+    for block in pkcs7_pad(plaintext, 16).chunks(16) {
+        let mixed: Vec<u8> = block.iter().zip(previous.iter()).map(|(x, y)| x ^ y).collect();
+        previous = encrypt_block(key, &mixed);
+        output.extend_from_slice(&previous);
+    }
+    output
+}
+
+/// Decrypts data with AES-128 in CBC mode, without removing any padding.
+///
+/// # Panics
+///
+/// Panics when the ciphertext isn't a multiple of 16 bytes long.
+///
+/// # Examples
+///
+/// ```
+/// let key = b"YELLOW SUBMARINE";
+/// let ciphertext = cryptopalslib::aes::cbc_encrypt(key, &[0; 16], b"attack at dawn");
+/// let output = cryptopalslib::aes::cbc_decrypt_raw(key, &[0; 16], &ciphertext);
+/// assert_eq!(output, b"attack at dawn\x02\x02");
+/// ```
+pub fn cbc_decrypt_raw(key: &[u8], iv: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    if ciphertext.len() % 16 != 0 {
+        panic!("CBC ciphertext length should be a multiple of 16.");
+    }
+
+    let mut previous = iv;
+    let mut output = vec!();
+    for block in ciphertext.chunks(16) {
+        let decrypted = decrypt_block(key, block);
+        output.extend(decrypted.iter().zip(previous.iter()).map(|(x, y)| x ^ y));
+        previous = block;
+    }
+    output
+}
+
+/// Decrypts data with AES-128 in CBC mode and removes its PKCS#7 padding.
+///
+/// # Examples
+///
+/// ```
+/// let key = b"YELLOW SUBMARINE";
+/// let ciphertext = cryptopalslib::aes::cbc_encrypt(key, &[0; 16], b"attack at dawn");
+/// let output = cryptopalslib::aes::cbc_decrypt(key, &[0; 16], &ciphertext);
+/// assert_eq!(output, Ok(b"attack at dawn".to_vec()));
+/// ```
+pub fn cbc_decrypt(key: &[u8], iv: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, Error> {
+    pkcs7_unpad(&cbc_decrypt_raw(key, iv, ciphertext))
+}
+
+/// Decrypts Base64-encoded lines that were encrypted with AES-128 in ECB
+/// mode. Returns `Error::NonAscii` with the plaintext if it has any bytes
+/// outside ASCII.
+///
+/// # Examples
+///
+/// ```
+/// let input = vec!("o3VBEciqmzUQswmiEMLdfPuhlv1XK0i0ww26jHAiaeY=".to_string());
+/// let output = cryptopalslib::aes::decrypt_base64_aes_ecb_128("TESTTESTTESTTEST", input);
+/// assert_eq!(output, Ok("This is encrypt.".to_string()));
+/// ```
+pub fn decrypt_base64_aes_ecb_128(key: &str, lines: Vec<String>) -> Result<String, Error> {
+    let base64_decoded = ::convert::base64_lines_to_hex(&lines);
+    let nums = ::convert::hex_string_to_decimal_pairs(&base64_decoded);
+
+    let t = symm::decrypt(symm::Type::AES_128_ECB, key.as_bytes(), vec!(), &nums);
+
+    if !t.is_ascii() {
+        return Err(Error::NonAscii(t));
+    }
+    Ok(t.into_iter().map(|x| x as char).collect())
+}
+
+/// Encrypts or decrypts data with AES-128 in CTR mode.
+///
+/// The keystream is built from a 64-bit little-endian nonce followed by a
+/// 64-bit little-endian block counter, which is the format the challenges use.
+/// Since CTR is a stream cipher, the same function is used for both
+/// directions.
+///
+/// # Examples
+///
+/// ```
+/// let key = b"YELLOW SUBMARINE";
+/// let ciphertext = cryptopalslib::aes::ctr(key, 0, b"attack at dawn");
+/// let plaintext = cryptopalslib::aes::ctr(key, 0, &ciphertext);
+/// assert_eq!(plaintext, b"attack at dawn");
+/// ```
+pub fn ctr(key: &[u8], nonce: u64, data: &[u8]) -> Vec<u8> {
+    let keystream = ctr_keystream(key, nonce, data.len());
+    data.iter().zip(keystream.iter()).map(|(x, y)| x ^ y).collect()
+}
+
+/// Generates `length` bytes of AES-128 CTR keystream.
+///
+/// # Examples
+///
+/// ```
+/// let keystream = cryptopalslib::aes::ctr_keystream(b"YELLOW SUBMARINE", 0, 20);
+/// assert_eq!(keystream.len(), 20);
+/// ```
+pub fn ctr_keystream(key: &[u8], nonce: u64, length: usize) -> Vec<u8> {
+    let mut keystream = vec!();
+    let mut counter: u64 = 0;
+    while keystream.len() < length {
+        let mut block = le_bytes(nonce);
+        block.extend(le_bytes(counter));
+        keystream.extend(encrypt_block(key, &block));
+        counter += 1;
+    }
+    keystream.truncate(length);
+    keystream
+}
+
+/// Replaces part of a CTR ciphertext (with a nonce of 0) with the encryption
+/// of `newtext`, as if seeking into the plaintext and overwriting it.
+///
+/// `newtext` may run past the end of the ciphertext, in which case the
+/// ciphertext grows.
+///
+/// # Panics
+///
+/// Panics when `offset` is past the end of the ciphertext.
+///
+/// # Examples
+///
+/// ```
+/// let key = b"YELLOW SUBMARINE";
+/// let ciphertext = cryptopalslib::aes::ctr(key, 0, b"attack at dawn");
+/// let edited = cryptopalslib::aes::edit(&ciphertext, key, 10, b"dusk");
+/// assert_eq!(cryptopalslib::aes::ctr(key, 0, &edited), b"attack at dusk");
+/// ```
+pub fn edit(ciphertext: &[u8], key: &[u8], offset: usize, newtext: &[u8]) -> Vec<u8> {
+    if offset > ciphertext.len() {
+        panic!("Can't edit past the end of the ciphertext.");
+    }
+
+    let end = offset + newtext.len();
+    let keystream = ctr_keystream(key, 0, end);
+    let mut output = ciphertext.to_vec();
+    output.truncate(offset);
+    output.extend(newtext.iter().zip(keystream[offset..].iter()).map(|(x, y)| x ^ y));
+    if end < ciphertext.len() {
+        output.extend_from_slice(&ciphertext[end..]);
+    }
+    output
+}
+
+/// Splits a 64-bit number into 8 little-endian bytes.
+fn le_bytes(num: u64) -> Vec<u8> {
+    (0..8).map(|x| (num >> (x * 8)) as u8).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use error::Error;
+    use openssl::crypto::symm;
+
+    #[test]
+    fn decrypt_base64_non_ascii() {
+        // the second is valid UTF-8, but still not ASCII
+        for plaintext in [&b"caf\xe9"[..], b"caf\xc3\xa9"].iter() {
+            let ciphertext = symm::encrypt(symm::Type::AES_128_ECB, b"YELLOW SUBMARINE", vec!(), plaintext);
+            let base64 = ::convert::hex_to_base64(&::convert::decimals_to_hex_string(ciphertext));
+            let output = super::decrypt_base64_aes_ecb_128("YELLOW SUBMARINE", vec!(base64));
+            assert_eq!(output, Err(Error::NonAscii(plaintext.to_vec())));
+        }
+    }
+
+    #[test]
+    fn ctr_challenge_18() {
+        let input = ::convert::hex_string_to_decimal_pairs(
+            &::convert::base64_to_hex("L77na/nrFsKvynd6HzOoG7GHTLXsTVu9qvY/2syLXzhPweyyMTJULu/6/kXX0KSvoOLSFQ=="));
+        let output = super::ctr(b"YELLOW SUBMARINE", 0, &input);
+        assert_eq!(&output[..], &b"Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby "[..]);
+    }
+
+    #[test]
+    fn cbc_matches_openssl() {
+        let key = b"YELLOW SUBMARINE";
+        let iv = b"0123456789abcdef";
+        let plaintext = b"a message that's a bit longer than two blocks";
+        let expected = ::openssl::crypto::symm::encrypt(::openssl::crypto::symm::Type::AES_128_CBC, key, iv.to_vec(), plaintext);
+        let ciphertext = super::cbc_encrypt(key, iv, plaintext);
+        assert_eq!(ciphertext, expected);
+        assert_eq!(super::cbc_decrypt(key, iv, &ciphertext), Ok(plaintext.to_vec()));
+    }
+
+    #[test]
+    fn edit_middle_and_past_end() {
+        let key = b"TESTTESTTESTTEST";
+        let ciphertext = super::ctr(key, 0, b"0123456789abcdefghij");
+        let edited = super::edit(&ciphertext, key, 18, b"XYZ!");
+        assert_eq!(&super::ctr(key, 0, &edited)[..], &b"0123456789abcdefghXYZ!"[..]);
+        let edited = super::edit(&ciphertext, key, 2, b"--");
+        assert_eq!(&super::ctr(key, 0, &edited)[..], &b"01--456789abcdefghij"[..]);
+    }
+
+    #[test]
+    fn ctr_roundtrip_with_nonce() {
+        let key = b"TESTTESTTESTTEST";
+        let ciphertext = super::ctr(key, 5, b"some text that spans more than one block");
+        assert!(&ciphertext[..] != &b"some text that spans more than one block"[..]);
+        let output = super::ctr(key, 5, &ciphertext);
+        assert_eq!(&output[..], &b"some text that spans more than one block"[..]);
+    }
+}
@@ -0,0 +1,213 @@
+use error::Error;
+
+/// A money transfer, as the bank's API server sees it (challenge 49).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transfer {
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+}
+
+/// Signs a request for one transfer, in the first version of the bank's
+/// API: `from=..&to=..&amount=..`, then a random IV, then the CBC-MAC.
+pub fn sign_transfer(key: &[u8], from: &str, to: &str, amount: u64) -> Vec<u8> {
+    let mut request = ::cookie::encode(&[("from", from), ("to", to), ("amount", &amount.to_string())]).into_bytes();
+    let iv = ::openssl::crypto::rand::rand_bytes(16);
+    let mac = ::mac::cbc_mac(key, &iv, &request);
+    request.extend_from_slice(&iv);
+    request.extend_from_slice(&mac);
+    request
+}
+
+/// Checks the MAC on a request in the first version of the API, using the
+/// IV that came with it, and reads the transfer.
+pub fn verify_transfer(key: &[u8], request: &[u8]) -> Result<Transfer, Error> {
+    if request.len() < 32 {
+        return Err(Error::InvalidEncoding);
+    }
+    let (message, iv, mac) = (&request[..request.len() - 32], &request[request.len() - 32..request.len() - 16], &request[request.len() - 16..]);
+    if !::mac::constant_time_compare(&::mac::cbc_mac(key, iv, message), mac) {
+        return Err(Error::InvalidMac);
+    }
+
+    let fields = ::cookie::parse(message);
+    match (::cookie::get(&fields, "from"), ::cookie::get(&fields, "to"), ::cookie::get(&fields, "amount")) {
+        (Some(from), Some(to), Some(amount)) => match amount.parse() {
+            Ok(amount) => Ok(Transfer { from: from.to_string(), to: to.to_string(), amount }),
+            Err(_) => Err(Error::InvalidEncoding),
+        },
+        _ => Err(Error::InvalidEncoding),
+    }
+}
+
+/// Signs a request for several transfers, in the second version of the
+/// API: `from=..&tx_list=to:amount;to:amount`, then the CBC-MAC with a zero
+/// IV.
+pub fn sign_transfers(key: &[u8], from: &str, transfers: &[(&str, u64)]) -> Vec<u8> {
+    let list = transfers.iter().map(|&(to, amount)| format!("{}:{}", to, amount)).collect::<Vec<String>>().join(";");
+    let mut request = ::cookie::encode(&[("from", from), ("tx_list", &list)]).into_bytes();
+    let mac = ::mac::cbc_mac(key, &[0; 16], &request);
+    request.extend_from_slice(&mac);
+    request
+}
+
+/// Checks the MAC on a request in the second version of the API and reads
+/// the transfers. Like plenty of real servers, it skips entries in the list
+/// that it can't make sense of, rather than rejecting the request.
+pub fn verify_transfers(key: &[u8], request: &[u8]) -> Result<Vec<Transfer>, Error> {
+    if request.len() < 16 {
+        return Err(Error::InvalidEncoding);
+    }
+    let (message, mac) = request.split_at(request.len() - 16);
+    if !::mac::constant_time_compare(&::mac::cbc_mac(key, &[0; 16], message), mac) {
+        return Err(Error::InvalidMac);
+    }
+
+    let fields = ::cookie::parse(message);
+    let (from, list) = match (::cookie::get(&fields, "from"), ::cookie::get(&fields, "tx_list")) {
+        (Some(from), Some(list)) => (from, list),
+        _ => return Err(Error::InvalidEncoding),
+    };
+    Ok(list.split(';').filter_map(|entry| {
+        let mut parts = entry.splitn(2, ':');
+        match (parts.next(), parts.next().and_then(|x| x.parse().ok())) {
+            (Some(to), Some(amount)) => Some(Transfer { from: from.to_string(), to: to.to_string(), amount }),
+            _ => None,
+        }
+    }).collect())
+}
+
+/// Rewrites who a first version request is from, without the key.
+///
+/// The IV only affects the first block, so XORing the IV with the same
+/// difference as the first block of the message leaves the MAC unchanged.
+/// Returns `None` unless `from` is the same length as the original sender
+/// and fits in the first block.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::attacks::cbc_mac::{forge_sender, sign_transfer, verify_transfer};
+///
+/// let key = b"YELLOW SUBMARINE";
+/// let request = sign_transfer(key, "1002", "1002", 1000000);
+/// let forged = forge_sender(&request, "1001").unwrap();
+/// assert_eq!(verify_transfer(key, &forged).unwrap().from, "1001");
+/// ```
+pub fn forge_sender(request: &[u8], from: &str) -> Option<Vec<u8>> {
+    if request.len() < 32 || !request.starts_with(b"from=") {
+        return None;
+    }
+    let message = &request[..request.len() - 32];
+    let end = message.iter().position(|&x| x == b'&').unwrap_or(message.len());
+    if end - 5 != from.len() || end > 16 {
+        return None;
+    }
+
+    let mut forged = request.to_vec();
+    forged[5..end].copy_from_slice(from.as_bytes());
+    let iv = request.len() - 32;
+    for i in 5..end {
+        forged[iv + i] ^= request[i] ^ forged[i];
+    }
+    Some(forged)
+}
+
+/// Builds a message with the same CBC-MAC as `second`, that starts with
+/// `first` and its padding. `first_mac` is the MAC of `first`, with a zero
+/// IV.
+///
+/// The chaining value after the padded `first` is its MAC, so XORing that
+/// into the first block of `second` puts the CBC state back where `second`
+/// starts, and the rest of `second` leads to its MAC.
+///
+/// # Panics
+///
+/// Panics if `second` is shorter than a block.
+pub fn splice(first: &[u8], first_mac: &[u8], second: &[u8]) -> Vec<u8> {
+    if second.len() < 16 {
+        panic!("Second message must be at least a block long");
+    }
+    let mut output = ::aes::pkcs7_pad(first, 16);
+    output.extend(second[..16].iter().zip(first_mac.iter()).map(|(x, y)| x ^ y));
+    output.extend_from_slice(&second[16..]);
+    output
+}
+
+/// Adds the transfers from an attacker's own second version request to a
+/// captured request from someone else, without the key (challenge 49).
+///
+/// The first block of `own` turns to garbage, so the transfers should start
+/// after it. Returns `None` if the garbage contains an `&`, which would end
+/// the transfer list early. The garbage only depends on the MAC of
+/// `captured`, so a different captured request gives different garbage.
+pub fn append_transfers(captured: &[u8], own: &[u8]) -> Option<Vec<u8>> {
+    if captured.len() < 16 || own.len() < 32 {
+        return None;
+    }
+    let (message, mac) = captured.split_at(captured.len() - 16);
+    let forged = splice(message, mac, own);
+    let junk = ::aes::pkcs7_pad(message, 16).len();
+    match forged[junk..junk + 16].contains(&b'&') {
+        true => None,
+        false => Some(forged),
+    }
+}
+
+/// Makes a message starting with `prefix` whose CBC-MAC, under a known key
+/// and a zero IV, matches `target`'s (challenge 50). With the key public,
+/// CBC-MAC is no good as a hash.
+///
+/// # Panics
+///
+/// Panics if `target` is shorter than a block.
+///
+/// # Examples
+///
+/// ```
+/// let key = b"YELLOW SUBMARINE";
+/// let target = b"alert('MZA who was that?');\n";
+/// let forged = cryptopalslib::attacks::cbc_mac::collide(key, target, b"alert('Ayo, the Wu is back!');//");
+/// assert!(forged.starts_with(b"alert('Ayo"));
+/// assert_eq!(cryptopalslib::mac::cbc_mac(key, &[0; 16], &forged), cryptopalslib::mac::cbc_mac(key, &[0; 16], target));
+/// ```
+pub fn collide(key: &[u8], target: &[u8], prefix: &[u8]) -> Vec<u8> {
+    splice(prefix, &::mac::cbc_mac(key, &[0; 16], prefix), target)
+}
+
+#[cfg(test)]
+mod test {
+    use error::Error;
+    use super::Transfer;
+
+    static KEY: &'static [u8] = b"YELLOW SUBMARINE";
+
+    #[test]
+    fn first_version() {
+        let request = super::sign_transfer(KEY, "2", "3", 100);
+        assert_eq!(super::verify_transfer(KEY, &request), Ok(Transfer { from: "2".to_string(), to: "3".to_string(), amount: 100 }));
+
+        let mut tampered = request.clone();
+        tampered[5] = b'1';
+        assert_eq!(super::verify_transfer(KEY, &tampered), Err(Error::InvalidMac));
+
+        let forged = super::forge_sender(&request, "1").unwrap();
+        assert_eq!(super::verify_transfer(KEY, &forged).map(|x| x.from), Ok("1".to_string()));
+        assert_eq!(super::forge_sender(&request, "10"), None);
+    }
+
+    #[test]
+    fn second_version() {
+        let captured = super::sign_transfers(KEY, "1", &[("3", 100), ("4", 250)]);
+        assert_eq!(super::verify_transfers(KEY, &captured).unwrap().len(), 2);
+
+        // "from=2&tx_list=2" fills the first block, so the garbage lands
+        // in the amount, and the real transfer follows
+        let own = super::sign_transfers(KEY, "2", &[("2", 1), ("2", 1000000)]);
+        let forged = super::append_transfers(&captured, &own).unwrap();
+
+        let transfers = super::verify_transfers(KEY, &forged).unwrap();
+        assert_eq!(transfers[0], Transfer { from: "1".to_string(), to: "3".to_string(), amount: 100 });
+        assert_eq!(transfers.last(), Some(&Transfer { from: "1".to_string(), to: "2".to_string(), amount: 1000000 }));
+    }
+}
@@ -0,0 +1,186 @@
+use bignum::BigUint;
+use dh::{Group, Message};
+use protocol::{Direction, Interceptor};
+
+/// Replaces both public keys with p (challenge 34).
+///
+/// Alice and Bob each end up computing p ^ x mod p, which is zero, so the
+/// session key is the one derived from zero. The encrypted messages are
+/// read and passed along untouched, so neither side notices.
+#[derive(Debug, Default)]
+pub struct ParameterInjection {
+    p: Option<BigUint>,
+    /// The plaintexts of the messages seen, in order.
+    pub recovered: Vec<Vec<u8>>,
+}
+
+impl ParameterInjection {
+    pub fn new() -> ParameterInjection {
+        ParameterInjection { p: None, recovered: vec!() }
+    }
+}
+
+impl Interceptor for ParameterInjection {
+    fn intercept(&mut self, _: Direction, message: Message) -> Vec<Message> {
+        match message {
+            Message::Handshake(group, _) => {
+                self.p = Some(group.p.clone());
+                let p = group.p.clone();
+                vec!(Message::Handshake(group, p))
+            },
+            Message::PublicKey(public) => match self.p {
+                Some(ref p) => vec!(Message::PublicKey(p.clone())),
+                None => vec!(Message::PublicKey(public)),
+            },
+            message => {
+                let key = ::dh::derive_key(&BigUint::zero());
+                if let Ok(plaintext) = ::protocol::decrypt_message(&key, &message) {
+                    self.recovered.push(plaintext);
+                }
+                vec!(message)
+            },
+        }
+    }
+}
+
+/// The malicious generators tried in challenge 35.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InjectedGenerator {
+    One,
+    P,
+    PMinusOne,
+}
+
+/// Swaps the generator Bob is offered for 1, p or p - 1 during group
+/// negotiation (challenge 35), and sends Bob that generator as Alice's
+/// public key.
+///
+/// Bob's public key B is then 1, 0 or ±1, and so is his shared secret
+/// B ^ b. Alice's secret is B ^ a, which is B unless B is p - 1, when it's
+/// 1 or p - 1 depending on a. Knowing both keys, Mallory decrypts each
+/// message and re-encrypts it for the other side.
+#[derive(Debug)]
+pub struct GeneratorInjection {
+    generator: InjectedGenerator,
+    group: Option<Group>,
+    alice_key: Option<Vec<u8>>,
+    bob_key: Option<Vec<u8>>,
+    bob_public: Option<BigUint>,
+    /// The plaintexts of the messages seen, in order.
+    pub recovered: Vec<Vec<u8>>,
+}
+
+impl GeneratorInjection {
+    pub fn new(generator: InjectedGenerator) -> GeneratorInjection {
+        GeneratorInjection {
+            generator,
+            group: None,
+            alice_key: None,
+            bob_key: None,
+            bob_public: None,
+            recovered: vec!(),
+        }
+    }
+
+    fn injected(&self, p: &BigUint) -> BigUint {
+        match self.generator {
+            InjectedGenerator::One => BigUint::one(),
+            InjectedGenerator::P => p.clone(),
+            InjectedGenerator::PMinusOne => p - &BigUint::one(),
+        }
+    }
+
+    /// Works out Alice's key from her first message, trying each secret she
+    /// could have ended up with.
+    fn find_alice_key(&mut self, message: &Message) {
+        let (p, public) = match (self.group.as_ref(), self.bob_public.as_ref()) {
+            (Some(group), Some(public)) => (group.p.clone(), public.clone()),
+            _ => return,
+        };
+        let candidates = match public == &p - &BigUint::one() {
+            true => vec!(BigUint::one(), public),
+            false => vec!(public),
+        };
+        // a wrong key still gives valid padding now and then, so prefer a
+        // key that gives readable text
+        let mut keys = candidates.iter().map(::dh::derive_key).filter_map(|key| {
+            ::protocol::decrypt_message(&key, message).ok().map(|plaintext| (key, plaintext))
+        }).collect::<Vec<_>>();
+        keys.sort_by_key(|key| !key.1.is_ascii());
+        self.alice_key = keys.into_iter().next().map(|(key, _)| key);
+    }
+
+    /// Decrypts a message under one key and re-encrypts it under another.
+    fn reencrypt(&mut self, message: Message, from: Option<Vec<u8>>, to: Option<Vec<u8>>) -> Message {
+        if let (Some(from), Some(to)) = (from, to) {
+            if let Ok(plaintext) = ::protocol::decrypt_message(&from, &message) {
+                let output = ::protocol::encrypt_message(&to, &plaintext);
+                self.recovered.push(plaintext);
+                return output;
+            }
+        }
+        message
+    }
+}
+
+impl Interceptor for GeneratorInjection {
+    fn intercept(&mut self, direction: Direction, message: Message) -> Vec<Message> {
+        match (direction, message) {
+            (Direction::ToBob, Message::Negotiate(group)) => {
+                let malicious = Group::new(group.p.clone(), self.injected(&group.p));
+                self.group = Some(group);
+                vec!(Message::Negotiate(malicious))
+            },
+            (Direction::ToBob, Message::PublicKey(public)) => match self.group {
+                Some(ref group) => vec!(Message::PublicKey(self.injected(&group.p))),
+                None => vec!(Message::PublicKey(public)),
+            },
+            (Direction::ToAlice, Message::PublicKey(public)) => {
+                // Bob's secret is the injected generator raised to his
+                // private key, which is exactly his public key
+                self.bob_key = Some(::dh::derive_key(&public));
+                self.bob_public = Some(public.clone());
+                vec!(Message::PublicKey(public))
+            },
+            (Direction::ToBob, message @ Message::Data { .. }) => {
+                if self.alice_key.is_none() {
+                    self.find_alice_key(&message);
+                }
+                let (from, to) = (self.alice_key.clone(), self.bob_key.clone());
+                vec!(self.reencrypt(message, from, to))
+            },
+            (Direction::ToAlice, message @ Message::Data { .. }) => {
+                let (from, to) = (self.bob_key.clone(), self.alice_key.clone());
+                vec!(self.reencrypt(message, from, to))
+            },
+            (_, message) => vec!(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use dh::Group;
+    use protocol::Handshake;
+    use super::{GeneratorInjection, InjectedGenerator, ParameterInjection};
+
+    #[test]
+    fn parameter_injection() {
+        let mut mallory = ParameterInjection::new();
+        let (alice, bob) = ::protocol::run(&Group::modp_1536(), Handshake::Direct, b"attack at dawn", &mut mallory);
+        assert_eq!(alice.unwrap(), b"attack at dawn");
+        assert_eq!(bob.unwrap(), b"attack at dawn");
+        assert_eq!(mallory.recovered, vec!(b"attack at dawn".to_vec(), b"attack at dawn".to_vec()));
+    }
+
+    #[test]
+    fn generator_injection() {
+        for &generator in [InjectedGenerator::One, InjectedGenerator::P, InjectedGenerator::PMinusOne].iter() {
+            let mut mallory = GeneratorInjection::new(generator);
+            let (alice, bob) = ::protocol::run(&Group::modp_1536(), Handshake::Negotiated, b"attack at dusk", &mut mallory);
+            assert_eq!(alice.unwrap(), b"attack at dusk");
+            assert_eq!(bob.unwrap(), b"attack at dusk");
+            assert_eq!(mallory.recovered, vec!(b"attack at dusk".to_vec(), b"attack at dusk".to_vec()));
+        }
+    }
+}
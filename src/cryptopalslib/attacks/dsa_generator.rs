@@ -0,0 +1,110 @@
+use bignum::BigUint;
+use dsa::{Parameters, PublicKey, Signature};
+use rsa::HashAlgorithm;
+
+/// The bad generators tried in challenge 45.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TamperedGenerator {
+    Zero,
+    PPlusOne,
+}
+
+impl TamperedGenerator {
+    /// Copies the parameters with g replaced.
+    pub fn apply(&self, params: &Parameters) -> Parameters {
+        let g = match *self {
+            TamperedGenerator::Zero => BigUint::zero(),
+            TamperedGenerator::PPlusOne => &params.p + &BigUint::one(),
+        };
+        Parameters { p: params.p.clone(), q: params.q.clone(), g }
+    }
+}
+
+/// Checks a DSA signature using whatever domain parameters come with the
+/// public key, without checking them or that r and s are in (0, q).
+pub fn trusting_verify(public: &PublicKey, hash: HashAlgorithm, message: &[u8], signature: &Signature) -> bool {
+    let params = &public.params;
+    let w = match signature.s.mod_inverse(&params.q) {
+        Some(w) => w,
+        None => return false,
+    };
+    let z = ::dsa::digest_to_integer(&hash.hash(message), &params.q);
+    let u1 = &(&z * &w) % &params.q;
+    let u2 = &(&signature.r * &w) % &params.q;
+    let v = &(&params.g.modpow(&u1, &params.p) * &public.y.modpow(&u2, &params.p)) % &params.p;
+    &v % &params.q == signature.r
+}
+
+/// Makes a signature that verifies for every message, once the key's g has
+/// been swapped for `generator` (challenge 45). No private key is needed.
+///
+/// With g = 0, g ^ u1 is 0, so v is always 0 and any signature with r = 0
+/// works, though only against a verifier that lets r = 0 through. With
+/// g = p + 1, g ^ u1 is 1, so for any z, r = y ^ z mod p mod q and
+/// s = r / z mod q make u2 = z and v = r. That one even gets past the range
+/// checks.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::attacks::dsa_generator::{magic_signature, trusting_verify, TamperedGenerator};
+/// use cryptopalslib::dsa::{Parameters, PrivateKey};
+/// use cryptopalslib::rsa::HashAlgorithm;
+///
+/// let mut public = PrivateKey::generate(&Parameters::challenge()).public_key();
+/// public.params = TamperedGenerator::PPlusOne.apply(&public.params);
+/// let signature = magic_signature(&public, TamperedGenerator::PPlusOne);
+/// assert!(trusting_verify(&public, HashAlgorithm::Sha1, b"Hello, world", &signature));
+/// assert!(trusting_verify(&public, HashAlgorithm::Sha1, b"Goodbye, world", &signature));
+/// ```
+pub fn magic_signature(public: &PublicKey, generator: TamperedGenerator) -> Signature {
+    let params = &public.params;
+    let bound = &params.q - &BigUint::one();
+    let z = || BigUint::random_below(&bound) + BigUint::one();
+    if generator == TamperedGenerator::Zero {
+        return Signature { r: BigUint::zero(), s: z() };
+    }
+    loop {
+        let z = z();
+        let r = &public.y.modpow(&z, &params.p) % &params.q;
+        if let Some(inverse) = z.mod_inverse(&params.q) {
+            let s = &(&r * &inverse) % &params.q;
+            if !r.is_zero() && !s.is_zero() {
+                return Signature { r, s };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use dsa::{Parameters, PrivateKey};
+    use rsa::HashAlgorithm;
+    use super::{magic_signature, trusting_verify, TamperedGenerator};
+
+    #[test]
+    fn honest_signatures() {
+        let key = PrivateKey::generate(&Parameters::challenge());
+        let signature = key.sign(HashAlgorithm::Sha1, b"Hello, world");
+        assert!(trusting_verify(&key.public_key(), HashAlgorithm::Sha1, b"Hello, world", &signature));
+        assert!(!trusting_verify(&key.public_key(), HashAlgorithm::Sha1, b"Goodbye, world", &signature));
+    }
+
+    #[test]
+    fn tampered_generators() {
+        let key = PrivateKey::generate(&Parameters::challenge());
+        for &generator in &[TamperedGenerator::Zero, TamperedGenerator::PPlusOne] {
+            let mut public = key.public_key();
+            public.params = generator.apply(&public.params);
+            let signature = magic_signature(&public, generator);
+            for message in &[&b"Hello, world"[..], b"Goodbye, world"] {
+                assert!(trusting_verify(&public, HashAlgorithm::Sha256, message, &signature));
+                // the range check stops r = 0, but nothing checks g
+                assert_eq!(public.verify(HashAlgorithm::Sha256, message, &signature), generator == TamperedGenerator::PPlusOne);
+            }
+
+            // the real parameters still reject it
+            assert!(!trusting_verify(&key.public_key(), HashAlgorithm::Sha256, b"Hello, world", &signature));
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use bignum::BigUint;
+use dsa::{Parameters, PrivateKey, PublicKey, Signature};
+use error::Error;
+use rsa::HashAlgorithm;
+use std::ops::Range;
+
+/// A signed message, as listed in the challenge 44 data file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedMessage {
+    pub message: String,
+    pub signature: Signature,
+    /// The integer that was signed: the message's hash, already cut down
+    /// to the size of q.
+    pub m: BigUint,
+}
+
+// (a - b) mod q, for a and b that might be larger than q
+fn sub_mod(a: &BigUint, b: &BigUint, q: &BigUint) -> BigUint {
+    &(&(a % q) + &(q - &(b % q))) % q
+}
+
+/// Computes the private key x from a signature of `m` and the nonce k it
+/// was made with: x = (s * k - m) / r mod q. Returns `None` if r has no
+/// inverse mod q.
+pub fn x_from_nonce(params: &Parameters, m: &BigUint, signature: &Signature, k: &BigUint) -> Option<BigUint> {
+    let r_inverse = signature.r.mod_inverse(&params.q)?;
+    let sk = &(&signature.s * k) % &params.q;
+    Some(&(&sub_mod(&sk, m, &params.q) * &r_inverse) % &params.q)
+}
+
+/// Recovers the private key behind a signature whose nonce was picked from
+/// `range` (challenge 43).
+///
+/// Every k in the range is tried by comparing g ^ k mod p mod q to r, which
+/// only takes a multiplication per k. A matching k gives a candidate x,
+/// which is checked against the public key.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::bignum::BigUint;
+/// use cryptopalslib::dsa::{Parameters, PrivateKey};
+/// use cryptopalslib::rsa::HashAlgorithm;
+///
+/// let key = PrivateKey::generate(&Parameters::generate(256, 64));
+/// let signature = key.sign_with_nonce(HashAlgorithm::Sha1, b"hello", &BigUint::from(1000)).unwrap();
+/// let found = cryptopalslib::attacks::dsa_nonce::recover_from_small_nonce(
+///     &key.public_key(), HashAlgorithm::Sha1, b"hello", &signature, 1..2000);
+/// assert_eq!(found, Some(key));
+/// ```
+pub fn recover_from_small_nonce(public: &PublicKey, hash: HashAlgorithm, message: &[u8], signature: &Signature, range: Range<u64>) -> Option<PrivateKey> {
+    let params = &public.params;
+    let m = ::dsa::digest_to_integer(&hash.hash(message), &params.q);
+    let mut power = params.g.modpow(&BigUint::from(range.start), &params.p);
+    for k in range {
+        if &power % &params.q == signature.r {
+            let k = BigUint::from(k);
+            if let Some(x) = x_from_nonce(params, &m, signature, &k) {
+                if params.g.modpow(&x, &params.p) == public.y {
+                    return Some(PrivateKey::from_x(params, x));
+                }
+            }
+        }
+        power = &(&power * &params.g) % &params.p;
+    }
+    None
+}
+
+/// Reads signed messages in the format of the challenge 44 data file: for
+/// each message, four lines of `msg: `, `s: ` and `r: ` in decimal, and
+/// `m: ` in hex.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::bignum::BigUint;
+///
+/// let text = "msg: Hello, world \ns: 12\nr: 34\nm: 0abc\n";
+/// let messages = cryptopalslib::attacks::dsa_nonce::parse_signed_messages(text).unwrap();
+/// assert_eq!(messages[0].message, "Hello, world ");
+/// assert_eq!(messages[0].signature.r, BigUint::from(34));
+/// assert_eq!(messages[0].m, BigUint::from(0xabc));
+/// ```
+pub fn parse_signed_messages(text: &str) -> Result<Vec<SignedMessage>, Error> {
+    let lines: Vec<&str> = text.lines().filter(|x| !x.trim().is_empty()).collect();
+    if lines.len() % 4 != 0 {
+        return Err(Error::InvalidEncoding);
+    }
+
+    let mut messages = vec!();
+    for group in lines.chunks(4) {
+        let field = |line: &str, name: &str| -> Result<String, Error> {
+            match line.starts_with(name) && line[name.len()..].starts_with(": ") {
+                true => Ok(line[name.len() + 2..].to_string()),
+                false => Err(Error::InvalidEncoding),
+            }
+        };
+        let decimal = |value: String| -> Result<BigUint, Error> {
+            match !value.is_empty() && value.chars().all(|x| x.is_ascii_digit()) {
+                true => Ok(BigUint::from_decimal(&value)),
+                false => Err(Error::InvalidEncoding),
+            }
+        };
+
+        let message = field(group[0], "msg")?;
+        let s = decimal(field(group[1], "s")?)?;
+        let r = decimal(field(group[2], "r")?)?;
+        let m = field(group[3], "m")?;
+        if m.is_empty() || !m.chars().all(|x| x.is_ascii_hexdigit()) {
+            return Err(Error::InvalidEncoding);
+        }
+        messages.push(SignedMessage { message, signature: Signature { r, s }, m: BigUint::from_hex(&m) });
+    }
+    Ok(messages)
+}
+
+/// Recovers the private key from a pair of signatures that share a nonce
+/// (challenge 44). Reused nonces show up as repeated values of r, and then
+///
+/// k = (m1 - m2) / (s1 - s2) mod q
+///
+/// which gives x as for a known nonce. Returns `None` if no pair gives an
+/// x that matches the public key.
+pub fn recover_from_reused_nonce(public: &PublicKey, messages: &[SignedMessage]) -> Option<PrivateKey> {
+    let params = &public.params;
+    for (i, first) in messages.iter().enumerate() {
+        for second in &messages[i + 1..] {
+            if first.signature.r != second.signature.r {
+                continue;
+            }
+            let ds = sub_mod(&first.signature.s, &second.signature.s, &params.q);
+            let k = match ds.mod_inverse(&params.q) {
+                Some(inverse) => &(&sub_mod(&first.m, &second.m, &params.q) * &inverse) % &params.q,
+                None => continue,
+            };
+            if let Some(x) = x_from_nonce(params, &first.m, &first.signature, &k) {
+                if params.g.modpow(&x, &params.p) == public.y {
+                    return Some(PrivateKey::from_x(params, x));
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use bignum::BigUint;
+    use dsa::{Parameters, PrivateKey};
+    use error::Error;
+    use rsa::HashAlgorithm;
+    use super::SignedMessage;
+
+    #[test]
+    fn small_nonce_out_of_range() {
+        let key = PrivateKey::generate(&Parameters::generate(256, 64));
+        let signature = key.sign_with_nonce(HashAlgorithm::Sha256, b"hello", &BigUint::from(5000)).unwrap();
+        let public = key.public_key();
+        assert_eq!(super::recover_from_small_nonce(&public, HashAlgorithm::Sha256, b"hello", &signature, 0..4000), None);
+        assert_eq!(super::recover_from_small_nonce(&public, HashAlgorithm::Sha256, b"hello", &signature, 4000..6000), Some(key));
+    }
+
+    #[test]
+    fn reused_nonce() {
+        let params = Parameters::challenge();
+        let key = PrivateKey::generate(&params);
+        let sign = |message: &str, k: u64| {
+            let signature = key.sign_with_nonce(HashAlgorithm::Sha1, message.as_bytes(), &BigUint::from(k)).unwrap();
+            let m = ::dsa::digest_to_integer(&HashAlgorithm::Sha1.hash(message.as_bytes()), &params.q);
+            SignedMessage { message: message.to_string(), signature, m }
+        };
+
+        let mut messages = vec!(sign("first", 1111), sign("second", 2222), sign("third", 3333));
+        assert_eq!(super::recover_from_reused_nonce(&key.public_key(), &messages), None);
+        messages.push(sign("fourth", 2222));
+        assert_eq!(super::recover_from_reused_nonce(&key.public_key(), &messages), Some(key));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(super::parse_signed_messages("msg: a\ns: 1\nr: 2\n"), Err(Error::InvalidEncoding));
+        assert_eq!(super::parse_signed_messages("msg: a\ns: 1\nr: x\nm: 3\n"), Err(Error::InvalidEncoding));
+        assert_eq!(super::parse_signed_messages("msg: a\nr: 1\ns: 2\nm: 3\n"), Err(Error::InvalidEncoding));
+        assert_eq!(super::parse_signed_messages("msg: a\ns: 1\nr: 2\nm: g\n"), Err(Error::InvalidEncoding));
+        assert_eq!(super::parse_signed_messages("").map(|x| x.len()), Ok(0));
+    }
+}
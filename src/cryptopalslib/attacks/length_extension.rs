@@ -0,0 +1,133 @@
+use hash::{Hasher, MerkleDamgard};
+use std::ops::Range;
+
+/// A message and MAC produced by a length extension attack.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Forgery {
+    /// The key length the forgery assumes.
+    pub key_length: usize,
+    /// The original message, its glue padding, and the extension.
+    pub message: Vec<u8>,
+    /// The MAC of the new message.
+    pub mac: Vec<u8>,
+}
+
+/// Returns the padding the hash appended after the key and message when
+/// the original MAC was computed.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::hash::sha1::Sha1;
+///
+/// let glue = cryptopalslib::attacks::length_extension::glue_padding::<Sha1>(16, 30);
+/// assert_eq!(16 + 30 + glue.len(), 64);
+/// ```
+pub fn glue_padding<H: MerkleDamgard>(key_length: usize, message_length: usize) -> Vec<u8> {
+    H::padding((key_length + message_length) as u64)
+}
+
+/// Forges a secret-prefix MAC for `message` with `extension` appended,
+/// assuming the secret key is `key_length` bytes long.
+///
+/// The hasher is restarted from the state held in `mac`, as if it had just
+/// processed the key, the message and the glue padding.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::hash::sha1::Sha1;
+/// use cryptopalslib::attacks::length_extension::forge;
+///
+/// let key = b"YELLOW SUBMARINE";
+/// let mac = cryptopalslib::mac::secret_prefix::<Sha1>(key, b"user=bob");
+/// let forgery = forge::<Sha1>(&mac, b"user=bob", b";admin=true", key.len());
+/// assert_eq!(forgery.mac, cryptopalslib::mac::secret_prefix::<Sha1>(key, &forgery.message));
+/// ```
+pub fn forge<H: MerkleDamgard>(mac: &[u8], message: &[u8], extension: &[u8], key_length: usize) -> Forgery {
+    let glue = glue_padding::<H>(key_length, message.len());
+
+    let mut forged_message = message.to_vec();
+    forged_message.extend_from_slice(&glue);
+
+    let processed = (key_length + forged_message.len()) as u64;
+    let mut hasher = Hasher::<H>::from_digest(mac, processed);
+    hasher.update(extension);
+
+    forged_message.extend_from_slice(extension);
+    Forgery {
+        key_length,
+        message: forged_message,
+        mac: hasher.finalize(),
+    }
+}
+
+/// Tries every key length in `key_lengths`, and returns the first forgery
+/// that `verify` accepts.
+///
+/// `verify` stands in for the server: it takes a message and MAC, and says
+/// whether the MAC is valid.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::hash::md4::Md4;
+/// use cryptopalslib::attacks::length_extension::forge_with_oracle;
+///
+/// let key = b"a secret";
+/// let mac = cryptopalslib::mac::secret_prefix::<Md4>(key, b"user=bob");
+/// let forgery = forge_with_oracle::<Md4, _>(&mac, b"user=bob", b";admin=true", 0..32, |message, mac| {
+///     cryptopalslib::mac::secret_prefix::<Md4>(key, message) == mac
+/// });
+/// assert_eq!(forgery.unwrap().key_length, 8);
+/// ```
+pub fn forge_with_oracle<H, F>(mac: &[u8], message: &[u8], extension: &[u8], key_lengths: Range<usize>, verify: F) -> Option<Forgery>
+    where H: MerkleDamgard, F: Fn(&[u8], &[u8]) -> bool {
+    key_lengths
+        .map(|x| forge::<H>(mac, message, extension, x))
+        .find(|x| verify(&x.message, &x.mac))
+}
+
+#[cfg(test)]
+mod test {
+    use hash::md4::Md4;
+    use hash::md5::Md5;
+    use hash::sha1::Sha1;
+    use hash::sha256::Sha256;
+    use mac::secret_prefix;
+
+    #[test]
+    fn forge_sha1_and_md4() {
+        let key = b"not a very secret key";
+        let message = b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+
+        let mac = secret_prefix::<Sha1>(key, message);
+        let forgery = super::forge::<Sha1>(&mac, message, b";admin=true", key.len());
+        assert_eq!(forgery.mac, secret_prefix::<Sha1>(key, &forgery.message));
+        assert!(forgery.message.ends_with(b";admin=true"));
+
+        let mac = secret_prefix::<Md4>(key, message);
+        let forgery = super::forge::<Md4>(&mac, message, b";admin=true", key.len());
+        assert_eq!(forgery.mac, secret_prefix::<Md4>(key, &forgery.message));
+    }
+
+    #[test]
+    fn forge_md5_and_sha256() {
+        let key = b"another key";
+        let mac = secret_prefix::<Md5>(key, b"user=bob");
+        let forgery = super::forge::<Md5>(&mac, b"user=bob", b";admin=true", key.len());
+        assert_eq!(forgery.mac, secret_prefix::<Md5>(key, &forgery.message));
+
+        let mac = secret_prefix::<Sha256>(key, b"user=bob");
+        let forgery = super::forge::<Sha256>(&mac, b"user=bob", b";admin=true", key.len());
+        assert_eq!(forgery.mac, secret_prefix::<Sha256>(key, &forgery.message));
+    }
+
+    #[test]
+    fn wrong_key_length_fails() {
+        let key = b"not a very secret key";
+        let mac = secret_prefix::<Sha1>(key, b"user=bob");
+        let forgery = super::forge::<Sha1>(&mac, b"user=bob", b";admin=true", key.len() + 1);
+        assert!(forgery.mac != secret_prefix::<Sha1>(key, &forgery.message));
+    }
+}
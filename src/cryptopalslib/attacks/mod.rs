@@ -0,0 +1,12 @@
+pub mod cbc_mac;
+pub mod dh_mitm;
+pub mod dsa_generator;
+pub mod dsa_nonce;
+pub mod length_extension;
+pub mod rsa_broadcast;
+pub mod rsa_padding_oracle;
+pub mod rsa_parity;
+pub mod rsa_signature_forgery;
+pub mod rsa_unpadded;
+pub mod srp;
+pub mod timing_leak;
@@ -0,0 +1,200 @@
+use bignum::BigUint;
+use rsa::{PrivateKey, PublicKey};
+
+/// Two moduli that share a prime factor. Factoring them is then just a
+/// division, so both keys are broken outright.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedFactor {
+    /// The indexes of the two keys.
+    pub first: usize,
+    pub second: usize,
+    /// The common factor, gcd(n1, n2).
+    pub factor: BigUint,
+}
+
+/// Reasons a broadcast attack can fail.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BroadcastError {
+    /// The keys don't all use the same public exponent.
+    MismatchedExponents,
+    /// There were fewer ciphertexts than the public exponent. The CRT
+    /// result might not be m ^ e, since m ^ e can be larger than the product
+    /// of the moduli.
+    NotEnoughCiphertexts,
+    /// Two moduli share a factor, so the CRT doesn't apply. Attack the keys
+    /// directly with `private_key_from_factor` instead.
+    SharedFactor(SharedFactor),
+    /// The combined value wasn't a perfect power, so the ciphertexts
+    /// weren't all the same message.
+    NoExactRoot,
+}
+
+/// Looks for a pair of moduli with a common factor by taking the gcd of
+/// every pair.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::bignum::BigUint;
+///
+/// let moduli = vec!(BigUint::from(11 * 13), BigUint::from(17 * 19), BigUint::from(13 * 23));
+/// let shared = cryptopalslib::attacks::rsa_broadcast::find_shared_factor(&moduli).unwrap();
+/// assert_eq!((shared.first, shared.second), (0, 2));
+/// assert_eq!(shared.factor, BigUint::from(13));
+/// ```
+pub fn find_shared_factor(moduli: &[BigUint]) -> Option<SharedFactor> {
+    let one = BigUint::one();
+    for first in 0..moduli.len() {
+        for second in first + 1..moduli.len() {
+            let factor = moduli[first].gcd(&moduli[second]);
+            if factor != one {
+                return Some(SharedFactor { first, second, factor });
+            }
+        }
+    }
+    None
+}
+
+/// Rebuilds a private key from its public key and one of its prime factors.
+/// Returns `None` if `factor` isn't a proper factor of n.
+pub fn private_key_from_factor(public: &PublicKey, factor: &BigUint) -> Option<PrivateKey> {
+    let one = BigUint::one();
+    if factor.is_zero() || *factor == one || *factor == public.n {
+        return None;
+    }
+    let (q, remainder) = public.n.div_rem(factor);
+    if !remainder.is_zero() {
+        return None;
+    }
+    let totient = &(factor - &one) * &(&q - &one);
+    let d = public.e.mod_inverse(&totient)?;
+    Some(PrivateKey { n: public.n.clone(), e: public.e.clone(), d, p: factor.clone(), q })
+}
+
+/// Solves x = r_i mod n_i for pairwise coprime moduli, using the Chinese
+/// remainder theorem. The result is the unique solution below the product
+/// of the moduli.
+///
+/// # Panics
+///
+/// Panics if the moduli aren't pairwise coprime.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::bignum::BigUint;
+///
+/// // x = 2 mod 3, x = 3 mod 5, x = 2 mod 7
+/// let residues = vec!(
+///     (BigUint::from(2), BigUint::from(3)),
+///     (BigUint::from(3), BigUint::from(5)),
+///     (BigUint::from(2), BigUint::from(7)),
+/// );
+/// assert_eq!(cryptopalslib::attacks::rsa_broadcast::crt(&residues), BigUint::from(23));
+/// ```
+pub fn crt(residues: &[(BigUint, BigUint)]) -> BigUint {
+    let product = residues.iter().fold(BigUint::one(), |x, (_, n)| &x * n);
+    let mut sum = BigUint::zero();
+    for (r, n) in residues {
+        // m_s_i is the product of all the other moduli
+        let others = &product / n;
+        let inverse = match (&others % n).mod_inverse(n) {
+            Some(inverse) => inverse,
+            None => panic!("Moduli aren't pairwise coprime"),
+        };
+        sum = &sum + &(&(r * &others) * &inverse);
+    }
+    &sum % &product
+}
+
+/// Recovers a message that was encrypted with textbook RSA under several
+/// keys with the same small exponent e (challenge 40).
+///
+/// With at least e ciphertexts, the CRT gives m ^ e modulo the product of
+/// the moduli. Since m is smaller than every modulus, m ^ e is smaller than
+/// the product, so that's m ^ e exactly, and an integer e-th root gives m.
+///
+/// Each intercept is a public key and the ciphertext encrypted under it.
+pub fn recover(intercepts: &[(PublicKey, BigUint)]) -> Result<BigUint, BroadcastError> {
+    let e = match intercepts.first() {
+        Some((key, _)) => key.e.clone(),
+        None => return Err(BroadcastError::NotEnoughCiphertexts),
+    };
+    if intercepts.iter().any(|(key, _)| key.e != e) {
+        return Err(BroadcastError::MismatchedExponents);
+    }
+    let e = match e.to_u64() {
+        Some(e) if e <= intercepts.len() as u64 => e as u32,
+        _ => return Err(BroadcastError::NotEnoughCiphertexts),
+    };
+
+    let moduli: Vec<BigUint> = intercepts.iter().map(|(key, _)| key.n.clone()).collect();
+    if let Some(shared) = find_shared_factor(&moduli) {
+        return Err(BroadcastError::SharedFactor(shared));
+    }
+
+    let residues: Vec<(BigUint, BigUint)> = intercepts.iter().map(|(key, c)| (c.clone(), key.n.clone())).collect();
+    let power = crt(&residues);
+    let m = power.nth_root(e);
+    match m.pow(e) == power {
+        true => Ok(m),
+        false => Err(BroadcastError::NoExactRoot),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bignum::BigUint;
+    use rsa::{PrivateKey, PublicKey};
+    use super::BroadcastError;
+
+    fn broadcast(message: &BigUint, keys: &[PublicKey]) -> Vec<(PublicKey, BigUint)> {
+        keys.iter().map(|key| (key.clone(), key.encrypt_raw(message))).collect()
+    }
+
+    #[test]
+    fn recover_with_three_and_four_keys() {
+        let message = BigUint::from_bytes_be(b"the magic words are squeamish ossifrage");
+        let keys: Vec<PublicKey> = (0..4).map(|_| PrivateKey::generate(512, &BigUint::from(3)).public_key()).collect();
+
+        assert_eq!(super::recover(&broadcast(&message, &keys[..3])), Ok(message.clone()));
+        assert_eq!(super::recover(&broadcast(&message, &keys)), Ok(message.clone()));
+        assert_eq!(super::recover(&broadcast(&message, &keys[..2])), Err(BroadcastError::NotEnoughCiphertexts));
+    }
+
+    #[test]
+    fn different_messages() {
+        let keys: Vec<PublicKey> = (0..3).map(|_| PrivateKey::generate(512, &BigUint::from(3)).public_key()).collect();
+        let mut intercepts = broadcast(&BigUint::from_bytes_be(b"one message"), &keys);
+        intercepts[2].1 = keys[2].encrypt_raw(&BigUint::from_bytes_be(b"another message"));
+        assert_eq!(super::recover(&intercepts), Err(BroadcastError::NoExactRoot));
+    }
+
+    #[test]
+    fn shared_factor() {
+        let e = BigUint::from(3);
+        let shared = PrivateKey::generate(512, &e);
+        let other = PrivateKey::generate(512, &e);
+        // a third key reusing one of the first key's primes
+        let q = loop {
+            let q = ::rsa::generate_prime(256);
+            if (&q - &BigUint::one()).gcd(&e) == BigUint::one() && q != shared.p {
+                break q;
+            }
+        };
+        let weak = PublicKey { n: &shared.p * &q, e: e.clone() };
+
+        let message = BigUint::from_bytes_be(b"shared primes");
+        let keys = vec!(shared.public_key(), other.public_key(), weak.clone());
+        let found = match super::recover(&broadcast(&message, &keys)) {
+            Err(BroadcastError::SharedFactor(found)) => found,
+            result => panic!("unexpected result {:?}", result),
+        };
+        assert_eq!((found.first, found.second), (0, 2));
+        assert_eq!(found.factor, shared.p);
+
+        let key = super::private_key_from_factor(&weak, &found.factor).unwrap();
+        assert_eq!(key.decrypt_raw(&weak.encrypt_raw(&message)), message);
+        assert_eq!(super::private_key_from_factor(&weak, &BigUint::from(7)), None);
+    }
+}
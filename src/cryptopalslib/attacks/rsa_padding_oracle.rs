@@ -0,0 +1,327 @@
+use bignum::BigUint;
+use rsa::{PrivateKey, PublicKey};
+
+/// A stand-in for a server that decrypts RSA ciphertexts and lets slip
+/// whether the plaintext starts 00 02, like a PKCS#1 v1.5 block should
+/// (challenges 47 and 48). Nothing past the first two bytes is checked.
+pub struct PaddingOracle {
+    key: PrivateKey,
+}
+
+impl PaddingOracle {
+    pub fn new(key: PrivateKey) -> PaddingOracle {
+        PaddingOracle { key }
+    }
+
+    /// The public key ciphertexts should be encrypted under.
+    pub fn public_key(&self) -> PublicKey {
+        self.key.public_key()
+    }
+
+    /// Whether a ciphertext's plaintext starts 00 02.
+    pub fn is_conforming(&self, c: &BigUint) -> bool {
+        let k = self.key.public_key().size();
+        let block = self.key.decrypt_raw(c).to_bytes_be_padded(k);
+        block[0] == 0 && block[1] == 2
+    }
+}
+
+/// The number of oracle queries spent in each step.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Queries {
+    /// Step 1, finding a blinding value that gives a conforming ciphertext.
+    pub blinding: usize,
+    pub step_2a: usize,
+    pub step_2b: usize,
+    pub step_2c: usize,
+}
+
+impl Queries {
+    pub fn total(&self) -> usize {
+        self.blinding + self.step_2a + self.step_2b + self.step_2c
+    }
+}
+
+/// The result of a successful attack.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recovery {
+    /// The whole padded plaintext block, as a number.
+    pub plaintext: BigUint,
+    pub queries: Queries,
+    /// How many times the intervals were narrowed down.
+    pub rounds: usize,
+}
+
+// the smallest integer not less than x / y
+fn ceil_div(x: &BigUint, y: &BigUint) -> BigUint {
+    &(&(x + y) - &BigUint::one()) / y
+}
+
+// sorts the intervals and merges any that overlap
+fn merge(mut intervals: Vec<(BigUint, BigUint)>) -> Vec<(BigUint, BigUint)> {
+    intervals.sort();
+    let mut merged: Vec<(BigUint, BigUint)> = vec!();
+    for (a, b) in intervals {
+        if let Some(last) = merged.last_mut() {
+            if a <= last.1 {
+                if b > last.1 {
+                    last.1 = b;
+                }
+                continue;
+            }
+        }
+        merged.push((a, b));
+    }
+    merged
+}
+
+/// The state of the attack between steps. `recover` runs the steps as the
+/// paper does, but they can also be driven one at a time, to compare the
+/// searches or see where the queries go.
+pub struct Attack<'a, O: FnMut(&BigUint) -> bool> {
+    public: &'a PublicKey,
+    oracle: O,
+    // the blinded ciphertext c0 and the blinding value s0
+    c0: BigUint,
+    s0: BigUint,
+    two_b: BigUint,
+    three_b: BigUint,
+    /// The last s found.
+    pub s: BigUint,
+    /// The intervals m0 could be in, inclusive at both ends.
+    pub intervals: Vec<(BigUint, BigUint)>,
+    pub queries: Queries,
+}
+
+impl<'a, O: FnMut(&BigUint) -> bool> Attack<'a, O> {
+    /// Step 1: blinds `c` with random values until the ciphertext conforms.
+    /// Ciphertexts of PKCS#1 v1.5 blocks already do, so no blinding is
+    /// needed for them.
+    pub fn new(public: &'a PublicKey, c: &BigUint, mut oracle: O) -> Attack<'a, O> {
+        let k = public.size();
+        let b = BigUint::one() << (8 * (k - 2));
+        let mut queries = Queries::default();
+
+        let mut s0 = BigUint::one();
+        let mut c0 = c.clone();
+        queries.blinding += 1;
+        while !oracle(&c0) {
+            s0 = BigUint::random_below(&public.n);
+            c0 = &(c * &s0.modpow(&public.e, &public.n)) % &public.n;
+            queries.blinding += 1;
+        }
+
+        let two_b = &b << 1;
+        let three_b = &two_b + &b;
+        let intervals = vec!((two_b.clone(), &three_b - &BigUint::one()));
+        Attack { public, oracle, c0, s0, two_b, three_b, s: BigUint::zero(), intervals, queries }
+    }
+
+    // whether c0 * s ^ e conforms
+    fn conforms(&mut self, s: &BigUint) -> bool {
+        let c = &(&self.c0 * &s.modpow(&self.public.e, &self.public.n)) % &self.public.n;
+        (self.oracle)(&c)
+    }
+
+    /// Step 2.a: finds the smallest s1 of at least n / 3B that conforms.
+    pub fn search_start(&mut self) {
+        let mut s = ceil_div(&self.public.n, &self.three_b);
+        loop {
+            self.queries.step_2a += 1;
+            if self.conforms(&s) {
+                break;
+            }
+            s = s + BigUint::one();
+        }
+        self.s = s;
+    }
+
+    /// Step 2.b: finds the next s that conforms, counting up from the last.
+    pub fn search_linear(&mut self) {
+        let mut s = &self.s + &BigUint::one();
+        loop {
+            self.queries.step_2b += 1;
+            if self.conforms(&s) {
+                break;
+            }
+            s = s + BigUint::one();
+        }
+        self.s = s;
+    }
+
+    /// Step 2.c: with a single interval [a, b] left, picks s from ranges
+    /// that roughly halve the interval, for growing r.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't exactly one interval.
+    pub fn search_one_interval(&mut self) {
+        if self.intervals.len() != 1 {
+            panic!("Step 2.c needs exactly one interval");
+        }
+        let (a, b) = self.intervals[0].clone();
+        let n = self.public.n.clone();
+        let mut r = ceil_div(&(&(&(&b * &self.s) - &self.two_b) << 1), &n);
+        loop {
+            let rn = &r * &n;
+            let low = ceil_div(&(&self.two_b + &rn), &b);
+            let high = &(&(&self.three_b - &BigUint::one()) + &rn) / &a;
+            let mut s = low;
+            while s <= high {
+                self.queries.step_2c += 1;
+                if self.conforms(&s) {
+                    self.s = s;
+                    return;
+                }
+                s = s + BigUint::one();
+            }
+            r = r + BigUint::one();
+        }
+    }
+
+    /// Step 3: narrows the intervals using the last s found. Returns false
+    /// if nothing is left, which only happens if the oracle lied.
+    pub fn narrow(&mut self) -> bool {
+        let n = &self.public.n;
+        let one = BigUint::one();
+        let mut narrowed = vec!();
+        for (a, b) in &self.intervals {
+            // r runs from (a * s - 3B + 1) / n to (b * s - 2B) / n
+            let as_ = a * &self.s;
+            let mut r = match as_ >= self.three_b {
+                true => ceil_div(&(&(&as_ - &self.three_b) + &one), n),
+                false => BigUint::zero(),
+            };
+            let r_max = &(&(b * &self.s) - &self.two_b) / n;
+            while r <= r_max {
+                let rn = &r * n;
+                let low = ceil_div(&(&self.two_b + &rn), &self.s);
+                let high = &(&(&self.three_b - &one) + &rn) / &self.s;
+                let low = if low > *a { low } else { a.clone() };
+                let high = if high < *b { high } else { b.clone() };
+                if low <= high {
+                    narrowed.push((low, high));
+                }
+                r = r + BigUint::one();
+            }
+        }
+        self.intervals = merge(narrowed);
+        !self.intervals.is_empty()
+    }
+
+    /// Step 4: if m0 is pinned down, unblinds it to give m.
+    pub fn solution(&self) -> Option<BigUint> {
+        match self.intervals.len() == 1 && self.intervals[0].0 == self.intervals[0].1 {
+            true => {
+                let inverse = self.s0.mod_inverse(&self.public.n)?;
+                Some(&(&self.intervals[0].0 * &inverse) % &self.public.n)
+            },
+            false => None,
+        }
+    }
+}
+
+/// Decrypts `c` with an oracle that says whether a ciphertext's plaintext
+/// starts 00 02, using Bleichenbacher's 1998 attack on PKCS#1 v1.5.
+///
+/// A conforming ciphertext means its plaintext is in [2B, 3B), for
+/// B = 2 ^ (8 * (k - 2)). Finding s values that make c * s ^ e conform
+/// narrows down where m * s mod n, and so m, can be, until only m is left.
+/// Returns `None` if the oracle's answers are inconsistent.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::attacks::rsa_padding_oracle::{recover, PaddingOracle};
+/// use cryptopalslib::bignum::BigUint;
+/// use cryptopalslib::rsa::PrivateKey;
+///
+/// let oracle = PaddingOracle::new(PrivateKey::generate(256, &BigUint::from(3)));
+/// let public = oracle.public_key();
+/// let c = BigUint::from_bytes_be(&public.encrypt(b"kick it, CC"));
+///
+/// let recovery = recover(&public, &c, |x| oracle.is_conforming(x)).unwrap();
+/// let block = recovery.plaintext.to_bytes_be_padded(public.size());
+/// assert_eq!(cryptopalslib::rsa::unpad_encryption(&block), Ok(b"kick it, CC".to_vec()));
+/// assert!(recovery.queries.total() > 0);
+/// ```
+pub fn recover<O: FnMut(&BigUint) -> bool>(public: &PublicKey, c: &BigUint, oracle: O) -> Option<Recovery> {
+    let mut attack = Attack::new(public, c, oracle);
+    attack.search_start();
+    let mut rounds = 0;
+    loop {
+        if !attack.narrow() {
+            return None;
+        }
+        rounds += 1;
+        if let Some(plaintext) = attack.solution() {
+            return Some(Recovery { plaintext, queries: attack.queries, rounds });
+        }
+        match attack.intervals.len() {
+            1 => attack.search_one_interval(),
+            _ => attack.search_linear(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bignum::BigUint;
+    use rsa::PrivateKey;
+    use super::PaddingOracle;
+
+    #[test]
+    fn merge_intervals() {
+        let interval = |a: u64, b: u64| (BigUint::from(a), BigUint::from(b));
+        let merged = super::merge(vec!(interval(10, 20), interval(1, 3), interval(15, 30), interval(3, 5), interval(31, 31)));
+        assert_eq!(merged, vec!(interval(1, 5), interval(10, 30), interval(31, 31)));
+    }
+
+    #[test]
+    fn steps() {
+        let key = PrivateKey::generate(256, &BigUint::from(3));
+        let oracle = PaddingOracle::new(key.clone());
+        let public = oracle.public_key();
+        let c = BigUint::from_bytes_be(&public.encrypt(b"kick it, CC"));
+        let m = key.decrypt_raw(&c);
+
+        let recovery = super::recover(&public, &c, |x| oracle.is_conforming(x)).unwrap();
+        assert_eq!(recovery.plaintext, m);
+        assert_eq!(recovery.queries.blinding, 1);
+        assert!(recovery.queries.step_2a > 0 && recovery.queries.step_2c > 0);
+
+        // m stays inside the intervals, which only ever shrink
+        let mut attack = super::Attack::new(&public, &c, |x| oracle.is_conforming(x));
+        attack.search_start();
+        let mut width = public.n.clone();
+        loop {
+            assert!(attack.narrow());
+            assert!(attack.intervals.iter().any(|(a, b)| *a <= m && m <= *b));
+            let total = attack.intervals.iter().fold(BigUint::zero(), |x, (a, b)| &x + &(b - a));
+            assert!(total <= width);
+            width = total;
+            if attack.solution().is_some() {
+                break;
+            }
+            match attack.intervals.len() {
+                1 => attack.search_one_interval(),
+                _ => attack.search_linear(),
+            }
+        }
+        assert_eq!(attack.solution(), Some(m));
+    }
+
+    #[test]
+    fn blinding() {
+        // a plaintext that isn't a PKCS#1 block needs blinding first
+        let key = PrivateKey::generate(256, &BigUint::from(3));
+        let oracle = PaddingOracle::new(key.clone());
+        let public = oracle.public_key();
+        let m = BigUint::from_bytes_be(b"not padded");
+        let c = public.encrypt_raw(&m);
+
+        let recovery = super::recover(&public, &c, |x| oracle.is_conforming(x)).unwrap();
+        assert_eq!(recovery.plaintext, m);
+        assert!(recovery.queries.blinding > 1);
+    }
+}
@@ -0,0 +1,99 @@
+use bignum::BigUint;
+use rsa::{PrivateKey, PublicKey};
+
+/// A stand-in for a server that decrypts textbook RSA ciphertexts and
+/// lets slip whether the plaintext is even (challenge 46).
+pub struct ParityOracle {
+    key: PrivateKey,
+}
+
+impl ParityOracle {
+    pub fn new(key: PrivateKey) -> ParityOracle {
+        ParityOracle { key }
+    }
+
+    /// The public key ciphertexts should be encrypted under.
+    pub fn public_key(&self) -> PublicKey {
+        self.key.public_key()
+    }
+
+    /// Whether a ciphertext decrypts to an even number.
+    pub fn is_even(&self, c: &BigUint) -> bool {
+        !self.key.decrypt_raw(c).is_odd()
+    }
+}
+
+/// Decrypts `c` with nothing but an oracle that says whether a ciphertext's
+/// plaintext is even.
+///
+/// Multiplying the ciphertext by 2 ^ e doubles the plaintext. The doubled
+/// plaintext is even unless it wrapped around the odd modulus, which says
+/// whether m was in the bottom or top half of [0, n). Doubling again splits
+/// that half, and so on, one bit of m per query.
+///
+/// After i queries m is known to be in [a * n / 2 ^ i, (a + 1) * n / 2 ^ i)
+/// for some integer a. Keeping a rather than the bounds themselves keeps
+/// them exact, so m is only rounded at the end. `progress` is called with
+/// the upper bound, rounded down, after each query.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::attacks::rsa_parity::ParityOracle;
+/// use cryptopalslib::bignum::BigUint;
+/// use cryptopalslib::rsa::PrivateKey;
+///
+/// let oracle = ParityOracle::new(PrivateKey::generate(512, &BigUint::from(65537)));
+/// let public = oracle.public_key();
+/// let c = public.encrypt_raw(&BigUint::from(1234567));
+///
+/// let mut queries = 0;
+/// let m = cryptopalslib::attacks::rsa_parity::recover(&public, &c, |x| oracle.is_even(x), |_| queries += 1);
+/// assert_eq!(m, BigUint::from(1234567));
+/// assert_eq!(queries, public.n.bits());
+/// ```
+pub fn recover<O, P>(public: &PublicKey, c: &BigUint, mut is_even: O, mut progress: P) -> BigUint
+    where O: FnMut(&BigUint) -> bool, P: FnMut(&BigUint) {
+    let double = BigUint::from(2).modpow(&public.e, &public.n);
+    let mut c = c.clone();
+    let mut a = BigUint::zero();
+    let one = BigUint::one();
+    let bits = public.n.bits();
+
+    for i in 1..bits + 1 {
+        c = &(&c * &double) % &public.n;
+        a = a << 1;
+        if !is_even(&c) {
+            a = &a + &one;
+        }
+        progress(&(&(&(&a + &one) * &public.n) >> i));
+    }
+
+    // the interval is now narrower than 1, so m is the lower bound rounded
+    // up
+    (&(&a * &public.n) + &((&one << bits) - &one)) >> bits
+}
+
+#[cfg(test)]
+mod test {
+    use bignum::BigUint;
+    use rsa::PrivateKey;
+    use super::ParityOracle;
+
+    #[test]
+    fn recover_messages() {
+        let oracle = ParityOracle::new(PrivateKey::generate(512, &BigUint::from(3)));
+        let public = oracle.public_key();
+        let largest = &public.n - &BigUint::one();
+        for m in &[BigUint::zero(), BigUint::one(), BigUint::from_bytes_be(b"parity"), largest] {
+            let c = public.encrypt_raw(m);
+            let mut bounds = vec!();
+            let output = super::recover(&public, &c, |x| oracle.is_even(x), |x| bounds.push(x.clone()));
+            assert_eq!(output, *m);
+
+            // the upper bound only ever comes down, and ends on m
+            assert!(bounds.windows(2).all(|x| x[1] <= x[0]));
+            assert_eq!(bounds.last(), Some(m));
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use bignum::BigUint;
+use der::Reader;
+use rsa::{HashAlgorithm, PublicKey};
+
+/// Checks a PKCS#1 v1.5 signature the way a lot of broken verifiers used to:
+/// by scanning past 00 01 ff .. ff 00 and parsing the DigestInfo, without
+/// checking that it fills the rest of the block. With a small e, that leaves
+/// room for garbage that makes signatures easy to forge.
+///
+/// The hash algorithm is taken from the DigestInfo, and can be SHA-1 or
+/// SHA-256.
+pub fn sloppy_verify(public: &PublicKey, message: &[u8], signature: &[u8]) -> bool {
+    let k = public.size();
+    let s = BigUint::from_bytes_be(signature);
+    if signature.len() != k || s >= public.n {
+        return false;
+    }
+
+    let block = public.encrypt_raw(&s).to_bytes_be_padded(k);
+    if block[0] != 0 || block[1] != 1 {
+        return false;
+    }
+    let padding = block[2..].iter().take_while(|&&x| x == 0xff).count();
+    if padding == 0 || block.get(2 + padding) != Some(&0) {
+        return false;
+    }
+
+    // the bug: whatever follows the DigestInfo is never looked at
+    let mut reader = Reader::new(&block[3 + padding..]);
+    match ::rsa::parse_digest_info(&mut reader) {
+        Ok((hash, digest)) => digest == hash.hash(message),
+        Err(_) => false,
+    }
+}
+
+/// Forges a signature that `sloppy_verify` accepts, without the private key
+/// (challenge 42).
+///
+/// The forged block is 00 01 ff .. ff 00 DigestInfo followed by garbage.
+/// Its e-th root, rounded up, is the signature: raised to the e it doesn't
+/// wrap the modulus, and only changes the garbage. Up to 8 bytes of ff are
+/// used, fewer if the garbage needs the room. Returns `None` if the key is
+/// too short for the hash with this exponent.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::bignum::BigUint;
+/// use cryptopalslib::rsa::{HashAlgorithm, PrivateKey};
+/// use cryptopalslib::attacks::rsa_signature_forgery::{forge, sloppy_verify};
+///
+/// let public = PrivateKey::generate(1024, &BigUint::from(3)).public_key();
+/// let signature = forge(&public, HashAlgorithm::Sha1, b"hi mom").unwrap();
+/// assert!(sloppy_verify(&public, b"hi mom", &signature));
+/// assert!(!public.verify(HashAlgorithm::Sha1, b"hi mom", &signature));
+/// ```
+pub fn forge(public: &PublicKey, hash: HashAlgorithm, message: &[u8]) -> Option<Vec<u8>> {
+    let e = match public.e.to_u64() {
+        Some(e) if e >> 32 == 0 => e as u32,
+        _ => return None,
+    };
+    let k = public.size();
+    let info = hash.digest_info(message);
+
+    for padding in (1..9).rev() {
+        let mut block = vec!(0, 1);
+        block.resize(2 + padding, 0xff);
+        block.push(0);
+        block.extend_from_slice(&info);
+        if block.len() > k {
+            continue;
+        }
+        let garbage = k - block.len();
+        block.resize(k, 0);
+
+        // the smallest root whose power is in [low, low + 2 ^ (8 * garbage))
+        let low = BigUint::from_bytes_be(&block);
+        let mut root = low.nth_root(e);
+        if root.pow(e) < low {
+            root = root + BigUint::one();
+        }
+        if root.pow(e) < &low + &(BigUint::one() << (8 * garbage)) && root < public.n {
+            return Some(root.to_bytes_be_padded(k));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use bignum::BigUint;
+    use rsa::{HashAlgorithm, PrivateKey, PublicKey};
+    use super::{forge, sloppy_verify};
+
+    #[test]
+    fn forged_sha1_signature() {
+        let key = PrivateKey::generate(1024, &BigUint::from(3));
+        let public = key.public_key();
+
+        // real signatures pass both verifiers
+        let signature = key.sign(HashAlgorithm::Sha1, b"hi mom");
+        assert!(sloppy_verify(&public, b"hi mom", &signature));
+        assert!(public.verify(HashAlgorithm::Sha1, b"hi mom", &signature));
+
+        let forged = forge(&public, HashAlgorithm::Sha1, b"hi mom").unwrap();
+        assert!(sloppy_verify(&public, b"hi mom", &forged));
+        assert!(!sloppy_verify(&public, b"hi dad", &forged));
+        assert!(!public.verify(HashAlgorithm::Sha1, b"hi mom", &forged));
+    }
+
+    #[test]
+    fn forged_sha256_signature() {
+        // a 1024-bit block doesn't leave enough garbage for the longer
+        // DigestInfo, but 2048 bits does
+        let public = PrivateKey::generate(1024, &BigUint::from(3)).public_key();
+        assert_eq!(forge(&public, HashAlgorithm::Sha256, b"hi mom"), None);
+
+        // the forgery never touches the factors, so any 2048-bit modulus
+        // will do and saves generating a key
+        let public = PublicKey { n: (BigUint::one() << 2047) + BigUint::from(0x1234567), e: BigUint::from(3) };
+        let forged = forge(&public, HashAlgorithm::Sha256, b"hi mom").unwrap();
+        assert!(sloppy_verify(&public, b"hi mom", &forged));
+        assert!(!public.verify(HashAlgorithm::Sha256, b"hi mom", &forged));
+    }
+}
@@ -0,0 +1,88 @@
+use bignum::BigUint;
+use hash::MerkleDamgard;
+use hash::sha256::Sha256;
+use rsa::{PrivateKey, PublicKey};
+use std::collections::HashSet;
+
+/// A stand-in for a server that decrypts textbook RSA ciphertexts for
+/// anyone, but only once each, so a captured ciphertext can't just be
+/// replayed (challenge 41).
+pub struct DecryptionServer {
+    key: PrivateKey,
+    // SHA-256 hashes of the ciphertexts already decrypted
+    seen: HashSet<Vec<u8>>,
+}
+
+impl DecryptionServer {
+    pub fn new(key: PrivateKey) -> DecryptionServer {
+        DecryptionServer { key, seen: HashSet::new() }
+    }
+
+    /// The public key ciphertexts should be encrypted under.
+    pub fn public_key(&self) -> PublicKey {
+        self.key.public_key()
+    }
+
+    /// Decrypts a ciphertext. Returns `None` if it's been decrypted before,
+    /// or isn't less than n.
+    pub fn decrypt(&mut self, c: &BigUint) -> Option<BigUint> {
+        if *c >= self.key.n || !self.seen.insert(Sha256::digest(&c.to_bytes_be())) {
+            return None;
+        }
+        Some(self.key.decrypt_raw(c))
+    }
+}
+
+/// Recovers the plaintext of `c` from a decryption oracle that won't
+/// decrypt `c` itself.
+///
+/// The ciphertext is blinded by multiplying it by s ^ e for a random s,
+/// which the oracle happily decrypts to s * m. Dividing by s mod n leaves m.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::bignum::BigUint;
+/// use cryptopalslib::rsa::PrivateKey;
+/// use cryptopalslib::attacks::rsa_unpadded::{recover, DecryptionServer};
+///
+/// let mut server = DecryptionServer::new(PrivateKey::generate(512, &BigUint::from(65537)));
+/// let public = server.public_key();
+/// let c = public.encrypt_raw(&BigUint::from(1234));
+/// assert!(server.decrypt(&c).is_some());
+/// assert!(server.decrypt(&c).is_none());
+/// assert_eq!(recover(&public, &c, |x| server.decrypt(x)), Some(BigUint::from(1234)));
+/// ```
+pub fn recover<F: FnMut(&BigUint) -> Option<BigUint>>(public: &PublicKey, c: &BigUint, mut decrypt: F) -> Option<BigUint> {
+    let two = BigUint::from(2);
+    let bound = &public.n - &two;
+    loop {
+        // s needs an inverse mod n, which any random s almost surely has
+        let s = BigUint::random_below(&bound) + &two;
+        let s_inverse = match s.mod_inverse(&public.n) {
+            Some(inverse) => inverse,
+            None => continue,
+        };
+        let blinded = &(&s.modpow(&public.e, &public.n) * c) % &public.n;
+        return decrypt(&blinded).map(|x| &(&x * &s_inverse) % &public.n);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bignum::BigUint;
+    use rsa::PrivateKey;
+    use super::DecryptionServer;
+
+    #[test]
+    fn recover_after_replay_is_refused() {
+        let mut server = DecryptionServer::new(PrivateKey::generate(1024, &BigUint::from(65537)));
+        let public = server.public_key();
+        let message = BigUint::from_bytes_be(b"{time: 1356304276, social: '555-55-5555'}");
+        let c = public.encrypt_raw(&message);
+
+        assert_eq!(server.decrypt(&c), Some(message.clone()));
+        assert_eq!(server.decrypt(&c), None);
+        assert_eq!(super::recover(&public, &c, |x| server.decrypt(x)), Some(message));
+    }
+}
@@ -0,0 +1,110 @@
+use bignum::BigUint;
+use error::Error;
+use srp::{Parameters, Server, SimpleServer};
+
+/// Logs in to an SRP server without knowing the password, by sending a
+/// client public key of `multiple` * N (challenge 37).
+///
+/// The server computes S = (A * v ^ u) ^ b mod N, which is zero whenever A
+/// is a multiple of N, so the session key is the hash of zero.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::srp::{Parameters, Server};
+///
+/// let parameters = Parameters::new();
+/// let mut server = Server::new(parameters.clone());
+/// server.register("alice@example.com", "not telling");
+/// assert!(cryptopalslib::attacks::srp::zero_key_login(&mut server, &parameters, "alice@example.com", 2));
+/// ```
+pub fn zero_key_login(server: &mut Server, parameters: &Parameters, email: &str, multiple: u64) -> bool {
+    let client_public = &parameters.n * &BigUint::from(multiple);
+    match server.start(email, &client_public) {
+        Ok((salt, _)) => {
+            let key = ::srp::session_key(&BigUint::zero());
+            server.verify(email, &::srp::proof(&key, &salt))
+        },
+        Err(_) => false,
+    }
+}
+
+/// Poses as a simplified SRP server from a man-in-the-middle position
+/// (challenge 38), and cracks the password offline from the client's proof.
+///
+/// It hands out an empty salt, b = 1 (so B = g) and u = 1. The client's
+/// proof then depends only on A, which it sent, and its password, so each
+/// guess costs one modular exponentiation.
+pub struct DictionaryAttacker {
+    parameters: Parameters,
+    captured: Option<(BigUint, Vec<u8>)>,
+}
+
+impl DictionaryAttacker {
+    pub fn new(parameters: Parameters) -> DictionaryAttacker {
+        DictionaryAttacker { parameters, captured: None }
+    }
+
+    /// Tries each candidate password against the captured proof, returning
+    /// the one that matches. Returns `None` if nothing has been captured yet
+    /// or no candidate matches.
+    pub fn crack<'a, I: IntoIterator<Item = &'a str>>(&self, candidates: I) -> Option<&'a str> {
+        let (client_public, client_proof) = match self.captured {
+            Some((ref client_public, ref client_proof)) => (client_public, client_proof),
+            None => return None,
+        };
+        let n = &self.parameters.n;
+        candidates.into_iter().find(|candidate| {
+            // S = (A * v ^ u) ^ b, with u = b = 1
+            let verifier = ::srp::verifier(&self.parameters, b"", candidate);
+            let key = ::srp::session_key(&(&(client_public * &verifier) % n));
+            ::srp::proof(&key, b"") == *client_proof
+        })
+    }
+}
+
+impl SimpleServer for DictionaryAttacker {
+    fn start(&mut self, _: &str, client_public: &BigUint) -> Result<(Vec<u8>, BigUint, BigUint), Error> {
+        self.captured = Some((client_public.clone(), vec!()));
+        Ok((vec!(), self.parameters.g.clone(), BigUint::one()))
+    }
+
+    fn verify(&mut self, _: &str, client_proof: &[u8]) -> bool {
+        if let Some((_, ref mut captured)) = self.captured {
+            *captured = client_proof.to_vec();
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use srp::{Parameters, Server};
+    use super::DictionaryAttacker;
+
+    #[test]
+    fn zero_key_login() {
+        let parameters = Parameters::new();
+        let mut server = Server::new(parameters.clone());
+        server.register("alice@example.com", "not telling");
+        for multiple in 0..4 {
+            assert!(super::zero_key_login(&mut server, &parameters, "alice@example.com", multiple));
+        }
+
+        let mut server = Server::strict(parameters.clone());
+        server.register("alice@example.com", "not telling");
+        assert!(!super::zero_key_login(&mut server, &parameters, "alice@example.com", 1));
+    }
+
+    #[test]
+    fn dictionary_attack() {
+        let parameters = Parameters::new();
+        let mut attacker = DictionaryAttacker::new(parameters.clone());
+        assert_eq!(attacker.crack(vec!("anything")), None);
+
+        assert!(!::srp::simple_login(&mut attacker, &parameters, "bob@example.com", "letmein"));
+        let words = vec!("password", "123456", "letmein", "dragon");
+        assert_eq!(attacker.crack(words), Some("letmein"));
+        assert_eq!(attacker.crack(vec!("qwerty", "monkey")), None);
+    }
+}
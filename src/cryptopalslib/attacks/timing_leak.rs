@@ -0,0 +1,134 @@
+/// Recovers a MAC from a comparison that leaks, through timing, how many
+/// leading bytes of a guess are correct.
+///
+/// `query` submits a guess and returns whether it was accepted and how long
+/// it took, in nanoseconds. Each byte is found by trying all 256 values in
+/// that position and keeping the slowest. Every guess is timed `samples`
+/// times; the fastest and slowest quarter of the timings are thrown out as
+/// outliers, and the rest are averaged.
+///
+/// Returns as soon as a guess is accepted, or after `length` bytes have been
+/// guessed.
+///
+/// # Examples
+///
+/// ```
+/// // a comparison that takes 100 units per matching byte
+/// let secret = [3, 1, 4, 1, 5];
+/// let output = cryptopalslib::attacks::timing_leak::recover_mac(5, 1, |guess| {
+///     let matching = guess.iter().zip(secret.iter()).take_while(|&(x, y)| x == y).count();
+///     (guess == &secret[..], 100 * matching as u64)
+/// });
+/// assert_eq!(output, secret);
+/// ```
+pub fn recover_mac<F: FnMut(&[u8]) -> (bool, u64)>(length: usize, samples: usize, query: F) -> Vec<u8> {
+    recover_mac_with(length, samples, trimmed_mean, query)
+}
+
+/// Like `recover_mac`, but combines each guess's timings with `estimate`,
+/// such as `trimmed_mean` or `median`.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::attacks::timing_leak::{median, recover_mac_with};
+///
+/// let secret = [3, 1, 4];
+/// let output = recover_mac_with(3, 3, median, |guess| {
+///     let matching = guess.iter().zip(secret.iter()).take_while(|&(x, y)| x == y).count();
+///     (guess == &secret[..], 100 * matching as u64)
+/// });
+/// assert_eq!(output, secret);
+/// ```
+pub fn recover_mac_with<E, F>(length: usize, samples: usize, estimate: E, mut query: F) -> Vec<u8>
+    where E: Fn(&mut [u64]) -> f64, F: FnMut(&[u8]) -> (bool, u64)
+{
+    let mut known = vec!();
+    while known.len() < length {
+        let mut best_byte = 0;
+        let mut best_time = 0.0;
+        for candidate in 0..256 {
+            let mut guess = known.clone();
+            guess.push(candidate as u8);
+            guess.resize(length, 0);
+
+            let mut timings = vec!();
+            for _ in 0..samples {
+                let (accepted, time) = query(&guess);
+                if accepted {
+                    return guess;
+                }
+                timings.push(time);
+            }
+
+            let time = estimate(&mut timings);
+            debug!("position {:?}, byte {:?}: {:?}", known.len(), candidate, time);
+            if time > best_time {
+                best_time = time;
+                best_byte = candidate as u8;
+            }
+        }
+        known.push(best_byte);
+    }
+    known
+}
+
+/// Averages the timings after dropping the fastest and slowest quarter.
+///
+/// # Examples
+///
+/// ```
+/// let mut timings = vec!(10, 11, 12, 9000);
+/// assert_eq!(cryptopalslib::attacks::timing_leak::trimmed_mean(&mut timings), 11.5);
+/// ```
+pub fn trimmed_mean(timings: &mut [u64]) -> f64 {
+    if timings.is_empty() {
+        return 0.0;
+    }
+    timings.sort();
+    let trim = timings.len() / 4;
+    let kept = &timings[trim..timings.len() - trim];
+    kept.iter().map(|&x| x as f64).sum::<f64>() / kept.len() as f64
+}
+
+/// Returns the median of the timings, averaging the middle two if there's
+/// an even number of them. Unlike `trimmed_mean`, up to half the timings
+/// can be outliers without moving it much.
+///
+/// # Examples
+///
+/// ```
+/// let mut timings = vec!(10, 9000, 12, 11);
+/// assert_eq!(cryptopalslib::attacks::timing_leak::median(&mut timings), 11.5);
+/// ```
+pub fn median(timings: &mut [u64]) -> f64 {
+    if timings.is_empty() {
+        return 0.0;
+    }
+    timings.sort();
+    let middle = timings.len() / 2;
+    if timings.len() % 2 == 0 {
+        (timings[middle - 1] as f64 + timings[middle] as f64) / 2.0
+    } else {
+        timings[middle] as f64
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn recover_despite_outliers() {
+        // every seventh query is a huge spike, which a plain average would
+        // mistake for a correct byte
+        let secret = [0xde, 0xad, 0xbe, 0xef];
+        let mut count = 0u64;
+        let output = super::recover_mac(4, 5, |guess| {
+            count += 1;
+            let matching = guess.iter().zip(secret.iter()).take_while(|&(x, y)| x == y).count() as u64;
+            let spike = if count % 7 == 0 { 100000 } else { 0 };
+            (guess == &secret[..], 1000 + 50 * matching + spike + count % 3)
+        });
+        assert_eq!(output, secret);
+    }
+}
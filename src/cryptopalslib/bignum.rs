@@ -0,0 +1,1067 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Rem, Shl, Shr, Sub};
+
+// below this many limbs, schoolbook multiplication beats Karatsuba
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// An arbitrary-precision unsigned integer.
+///
+/// The number is stored as 32-bit limbs, least significant first, with no
+/// leading zero limbs, so zero has no limbs at all.
+///
+/// The arithmetic operators are implemented for both values and
+/// references. Subtracting a larger number from a smaller one and dividing
+/// by zero both panic.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::bignum::BigUint;
+///
+/// let a = BigUint::from_hex("ffffffffffffffff");
+/// let b = &a * &a + BigUint::from(1);
+/// assert_eq!(b.to_hex(), "fffffffffffffffe0000000000000002");
+/// assert_eq!(&b % &a, BigUint::from(1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    /// Zero.
+    pub fn zero() -> BigUint {
+        BigUint { limbs: vec!() }
+    }
+
+    /// One.
+    pub fn one() -> BigUint {
+        BigUint { limbs: vec!(1) }
+    }
+
+    fn from_limbs(limbs: Vec<u32>) -> BigUint {
+        let mut limbs = limbs;
+        normalize(&mut limbs);
+        BigUint { limbs }
+    }
+
+    /// Reads a big-endian byte string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let number = cryptopalslib::bignum::BigUint::from_bytes_be(&[1, 0]);
+    /// assert_eq!(number, cryptopalslib::bignum::BigUint::from(256));
+    /// ```
+    pub fn from_bytes_be(bytes: &[u8]) -> BigUint {
+        let mut limbs = Vec::with_capacity(bytes.len() / 4 + 1);
+        for chunk in bytes.rchunks(4) {
+            let mut limb = 0u32;
+            for &byte in chunk {
+                limb = (limb << 8) | byte as u32;
+            }
+            limbs.push(limb);
+        }
+        BigUint::from_limbs(limbs)
+    }
+
+    /// Writes the number as a big-endian byte string, with no leading zero
+    /// bytes. Zero is written as a single zero byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let number = cryptopalslib::bignum::BigUint::from(0x10203);
+    /// assert_eq!(number.to_bytes_be(), vec!(1, 2, 3));
+    /// ```
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = vec!();
+        for &limb in self.limbs.iter().rev() {
+            bytes.extend_from_slice(&[(limb >> 24) as u8, (limb >> 16) as u8, (limb >> 8) as u8, limb as u8]);
+        }
+        let leading = bytes.iter().take_while(|&&x| x == 0).count();
+        match leading == bytes.len() {
+            true => vec!(0),
+            false => bytes[leading..].to_vec(),
+        }
+    }
+
+    /// Writes the number as a big-endian byte string, left-padded with zeros
+    /// to `length` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number doesn't fit in `length` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let number = cryptopalslib::bignum::BigUint::from(0x102);
+    /// assert_eq!(number.to_bytes_be_padded(4), vec!(0, 0, 1, 2));
+    /// ```
+    pub fn to_bytes_be_padded(&self, length: usize) -> Vec<u8> {
+        let bytes = match self.is_zero() {
+            true => vec!(),
+            false => self.to_bytes_be(),
+        };
+        if bytes.len() > length {
+            panic!("Number doesn't fit in {} bytes", length);
+        }
+        let mut output = vec!(0; length - bytes.len());
+        output.extend_from_slice(&bytes);
+        output
+    }
+
+    /// Parses a string of hex digits. The string doesn't need to have an
+    /// even length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is empty or contains anything but hex digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let number = cryptopalslib::bignum::BigUint::from_hex("fFf");
+    /// assert_eq!(number, cryptopalslib::bignum::BigUint::from(4095));
+    /// ```
+    pub fn from_hex(hex: &str) -> BigUint {
+        if hex.is_empty() || !hex.chars().all(|x| x.is_ascii_hexdigit()) {
+            panic!("Not a valid hex string: {:?}", hex);
+        }
+        let bytes = match hex.len() % 2 {
+            0 => ::convert::hex_string_to_decimal_pairs(hex),
+            _ => ::convert::hex_string_to_decimal_pairs(&format!("0{}", hex)),
+        };
+        BigUint::from_bytes_be(&bytes)
+    }
+
+    /// Writes the number as lowercase hex digits, with no leading zeros.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let number = cryptopalslib::bignum::BigUint::from(4095);
+    /// assert_eq!(number.to_hex(), "fff");
+    /// ```
+    pub fn to_hex(&self) -> String {
+        let hex = ::convert::decimals_to_hex_string(self.to_bytes_be());
+        match hex.starts_with('0') && hex.len() > 1 {
+            true => hex[1..].to_string(),
+            false => hex,
+        }
+    }
+
+    /// Parses a string of decimal digits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is empty or contains anything but decimal digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let number = cryptopalslib::bignum::BigUint::from_decimal("18446744073709551616");
+    /// assert_eq!(number.to_hex(), "10000000000000000");
+    /// ```
+    pub fn from_decimal(decimal: &str) -> BigUint {
+        if decimal.is_empty() || !decimal.chars().all(|x| x.is_ascii_digit()) {
+            panic!("Not a valid decimal string: {:?}", decimal);
+        }
+        let mut limbs = vec!();
+        for chunk in decimal.as_bytes().chunks(9) {
+            let mut multiplier = 1;
+            let mut value = 0;
+            for &digit in chunk {
+                multiplier *= 10;
+                value = value * 10 + (digit - b'0') as u32;
+            }
+            mul_add_small(&mut limbs, multiplier, value);
+        }
+        BigUint::from_limbs(limbs)
+    }
+
+    /// Picks a random number in the range [0, bound), using OpenSSL's
+    /// random number generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cryptopalslib::bignum::BigUint;
+    ///
+    /// let bound = BigUint::from(1000);
+    /// assert!(BigUint::random_below(&bound) < bound);
+    /// ```
+    pub fn random_below(bound: &BigUint) -> BigUint {
+        if bound.is_zero() {
+            panic!("Can't pick a number below zero");
+        }
+        // the extra bytes make the bias from reducing mod bound negligible
+        let bytes = ::openssl::crypto::rand::rand_bytes(bound.bits() / 8 + 9);
+        &BigUint::from_bytes_be(&bytes) % bound
+    }
+
+    /// Returns the value as a `u64`, if it fits.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 => Some(self.limbs[0] as u64),
+            2 => Some(((self.limbs[1] as u64) << 32) | self.limbs[0] as u64),
+            _ => None,
+        }
+    }
+
+    /// Whether the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Whether the number is odd.
+    pub fn is_odd(&self) -> bool {
+        self.limbs.first().map_or(false, |&x| x & 1 == 1)
+    }
+
+    /// The number of bits needed to represent the number. Zero needs none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(cryptopalslib::bignum::BigUint::from(255).bits(), 8);
+    /// assert_eq!(cryptopalslib::bignum::BigUint::from(256).bits(), 9);
+    /// ```
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(&top) => self.limbs.len() * 32 - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// Returns bit `index`, counting from the least significant bit.
+    pub fn bit(&self, index: usize) -> bool {
+        match self.limbs.get(index / 32) {
+            Some(&limb) => (limb >> (index % 32)) & 1 == 1,
+            None => false,
+        }
+    }
+
+    /// Divides by `divisor`, returning the quotient and remainder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cryptopalslib::bignum::BigUint;
+    ///
+    /// let (quotient, remainder) = BigUint::from(100).div_rem(&BigUint::from(7));
+    /// assert_eq!(quotient, BigUint::from(14));
+    /// assert_eq!(remainder, BigUint::from(2));
+    /// ```
+    pub fn div_rem(&self, divisor: &BigUint) -> (BigUint, BigUint) {
+        if divisor.is_zero() {
+            panic!("Division by zero");
+        }
+        if self < divisor {
+            return (BigUint::zero(), self.clone());
+        }
+        let (quotient, remainder) = div_rem_limbs(&self.limbs, &divisor.limbs);
+        (BigUint::from_limbs(quotient), BigUint::from_limbs(remainder))
+    }
+
+    /// Raises the number to a small power.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let number = cryptopalslib::bignum::BigUint::from(3).pow(40);
+    /// assert_eq!(number, cryptopalslib::bignum::BigUint::from(12157665459056928801));
+    /// ```
+    pub fn pow(&self, exponent: u32) -> BigUint {
+        let mut output = BigUint::one();
+        let mut base = self.clone();
+        let mut exponent = exponent;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                output = &output * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        output
+    }
+
+    /// Computes `self ^ exponent mod modulus`.
+    ///
+    /// Odd moduli, which covers everything in RSA and Diffie-Hellman, use
+    /// Montgomery multiplication. Even moduli fall back to dividing after
+    /// every step.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cryptopalslib::bignum::BigUint;
+    ///
+    /// let output = BigUint::from(4).modpow(&BigUint::from(13), &BigUint::from(497));
+    /// assert_eq!(output, BigUint::from(445));
+    /// ```
+    pub fn modpow(&self, exponent: &BigUint, modulus: &BigUint) -> BigUint {
+        if modulus.is_zero() {
+            panic!("Modulus can't be zero");
+        }
+        if *modulus == BigUint::one() {
+            return BigUint::zero();
+        }
+        match modulus.is_odd() {
+            true => Montgomery::new(modulus).modpow(self, exponent),
+            false => {
+                let base = self % modulus;
+                let mut output = BigUint::one();
+                for index in (0..exponent.bits()).rev() {
+                    output = &(&output * &output) % modulus;
+                    if exponent.bit(index) {
+                        output = &(&output * &base) % modulus;
+                    }
+                }
+                output
+            }
+        }
+    }
+
+    /// Computes the greatest common divisor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cryptopalslib::bignum::BigUint;
+    ///
+    /// assert_eq!(BigUint::from(84).gcd(&BigUint::from(36)), BigUint::from(12));
+    /// ```
+    pub fn gcd(&self, other: &BigUint) -> BigUint {
+        let mut a = self.clone();
+        let mut b = other.clone();
+        while !b.is_zero() {
+            let remainder = &a % &b;
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+
+    /// Finds the inverse of the number modulo `modulus`, using the extended
+    /// Euclidean algorithm. Returns `None` if the two aren't coprime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cryptopalslib::bignum::BigUint;
+    ///
+    /// let inverse = BigUint::from(17).mod_inverse(&BigUint::from(3120));
+    /// assert_eq!(inverse, Some(BigUint::from(2753)));
+    /// assert_eq!(BigUint::from(6).mod_inverse(&BigUint::from(9)), None);
+    /// ```
+    pub fn mod_inverse(&self, modulus: &BigUint) -> Option<BigUint> {
+        if modulus.is_zero() {
+            return None;
+        }
+        // the coefficients are kept reduced mod modulus, so they never go
+        // negative
+        let mut old_r = self % modulus;
+        let mut r = modulus.clone();
+        let mut old_s = BigUint::one();
+        let mut s = BigUint::zero();
+        while !r.is_zero() {
+            let (quotient, remainder) = old_r.div_rem(&r);
+            old_r = r;
+            r = remainder;
+
+            let product = &(&quotient * &s) % modulus;
+            let next_s = &(&old_s + modulus) - &product;
+            old_s = s;
+            s = &next_s % modulus;
+        }
+        match old_r == BigUint::one() {
+            true => Some(&old_s % modulus),
+            false => None,
+        }
+    }
+
+    /// Computes the `n`th root, rounded down, using Newton's method.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cryptopalslib::bignum::BigUint;
+    ///
+    /// assert_eq!(BigUint::from(1000).nth_root(3), BigUint::from(10));
+    /// assert_eq!(BigUint::from(999).nth_root(3), BigUint::from(9));
+    /// ```
+    pub fn nth_root(&self, n: u32) -> BigUint {
+        if n == 0 {
+            panic!("Can't take the zeroth root");
+        }
+        if n == 1 || self.is_zero() {
+            return self.clone();
+        }
+
+        // start above the root, so every step moves down towards it
+        let n_big = BigUint::from(n as u64);
+        let n_minus_one = BigUint::from(n as u64 - 1);
+        let bits = (self.bits() + n as usize - 1) / n as usize;
+        let mut x = BigUint::one() << bits;
+        loop {
+            let y = &(&(&n_minus_one * &x) + &(self / &x.pow(n - 1))) / &n_big;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    /// Computes the cube root, rounded down.
+    pub fn cube_root(&self) -> BigUint {
+        self.nth_root(3)
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> BigUint {
+        BigUint::from_limbs(vec!(value as u32, (value >> 32) as u32))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        cmp_limbs(&self.limbs, &other.limbs)
+    }
+}
+
+impl fmt::Display for BigUint {
+    /// Writes the number in decimal.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return f.pad("0");
+        }
+        // peel off nine decimal digits at a time
+        let mut limbs = self.limbs.clone();
+        let mut chunks = vec!();
+        while !limbs.is_empty() {
+            chunks.push(div_rem_small(&mut limbs, 1_000_000_000));
+        }
+        let mut output = chunks.pop().unwrap().to_string();
+        for chunk in chunks.iter().rev() {
+            output.push_str(&format!("{:09}", chunk));
+        }
+        f.pad(&output)
+    }
+}
+
+impl fmt::LowerHex for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&self.to_hex())
+    }
+}
+
+impl<'a, 'b> Add<&'b BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        BigUint::from_limbs(add_limbs(&self.limbs, &other.limbs))
+    }
+}
+
+impl<'a, 'b> Sub<&'b BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn sub(self, other: &BigUint) -> BigUint {
+        if self < other {
+            panic!("Subtraction would be negative");
+        }
+        BigUint::from_limbs(sub_limbs(&self.limbs, &other.limbs))
+    }
+}
+
+impl<'a, 'b> Mul<&'b BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        BigUint::from_limbs(mul_limbs(&self.limbs, &other.limbs))
+    }
+}
+
+impl<'a, 'b> Div<&'b BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn div(self, other: &BigUint) -> BigUint {
+        self.div_rem(other).0
+    }
+}
+
+impl<'a, 'b> Rem<&'b BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn rem(self, other: &BigUint) -> BigUint {
+        self.div_rem(other).1
+    }
+}
+
+// forwards the operators on values to the ones on references
+macro_rules! forward_binop {
+    ($trait_name:ident, $method:ident) => {
+        impl $trait_name<BigUint> for BigUint {
+            type Output = BigUint;
+
+            fn $method(self, other: BigUint) -> BigUint {
+                (&self).$method(&other)
+            }
+        }
+
+        impl<'a> $trait_name<&'a BigUint> for BigUint {
+            type Output = BigUint;
+
+            fn $method(self, other: &BigUint) -> BigUint {
+                (&self).$method(other)
+            }
+        }
+
+        impl<'a> $trait_name<BigUint> for &'a BigUint {
+            type Output = BigUint;
+
+            fn $method(self, other: BigUint) -> BigUint {
+                self.$method(&other)
+            }
+        }
+    }
+}
+
+forward_binop!(Add, add);
+forward_binop!(Sub, sub);
+forward_binop!(Mul, mul);
+forward_binop!(Div, div);
+forward_binop!(Rem, rem);
+
+impl<'a> Shl<usize> for &'a BigUint {
+    type Output = BigUint;
+
+    fn shl(self, shift: usize) -> BigUint {
+        let mut limbs = vec!(0; shift / 32);
+        limbs.extend(shl_bits(&self.limbs, (shift % 32) as u32));
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Shl<usize> for BigUint {
+    type Output = BigUint;
+
+    fn shl(self, shift: usize) -> BigUint {
+        &self << shift
+    }
+}
+
+impl<'a> Shr<usize> for &'a BigUint {
+    type Output = BigUint;
+
+    fn shr(self, shift: usize) -> BigUint {
+        if shift / 32 >= self.limbs.len() {
+            return BigUint::zero();
+        }
+        BigUint::from_limbs(shr_bits(&self.limbs[shift / 32..], (shift % 32) as u32))
+    }
+}
+
+impl Shr<usize> for BigUint {
+    type Output = BigUint;
+
+    fn shr(self, shift: usize) -> BigUint {
+        &self >> shift
+    }
+}
+
+/// Precomputed values for Montgomery multiplication modulo an odd number.
+///
+/// Numbers are kept multiplied by R = 2^(32 * limbs) mod n, which lets
+/// reduction after each multiplication be done with shifts instead of
+/// division.
+struct Montgomery {
+    modulus: Vec<u32>,
+    // -modulus^-1 mod 2^32
+    inverse: u32,
+    // R^2 mod modulus, for converting into Montgomery form
+    r_squared: Vec<u32>,
+}
+
+impl Montgomery {
+    fn new(modulus: &BigUint) -> Montgomery {
+        let limbs = modulus.limbs.clone();
+
+        // Newton's iteration doubles the number of correct bits each step,
+        // and any odd number is its own inverse mod 8
+        let mut inverse = limbs[0];
+        for _ in 0..4 {
+            inverse = inverse.wrapping_mul(2u32.wrapping_sub(limbs[0].wrapping_mul(inverse)));
+        }
+
+        let r_squared = &(BigUint::one() << (64 * limbs.len())) % modulus;
+        Montgomery {
+            r_squared: pad_limbs(&r_squared.limbs, limbs.len()),
+            modulus: limbs,
+            inverse: inverse.wrapping_neg(),
+        }
+    }
+
+    /// Computes a * b / R mod n, for a and b already reduced mod n.
+    fn multiply(&self, a: &[u32], b: &[u32]) -> Vec<u32> {
+        let size = self.modulus.len();
+        let mut t = vec!(0u32; size + 2);
+        for i in 0..size {
+            // t += a[i] * b
+            let mut carry = 0u64;
+            for j in 0..size {
+                let x = t[j] as u64 + a[i] as u64 * b[j] as u64 + carry;
+                t[j] = x as u32;
+                carry = x >> 32;
+            }
+            let x = t[size] as u64 + carry;
+            t[size] = x as u32;
+            t[size + 1] = (x >> 32) as u32;
+
+            // add a multiple of n that clears the lowest limb, then shift
+            // it out
+            let m = t[0].wrapping_mul(self.inverse);
+            let x = t[0] as u64 + m as u64 * self.modulus[0] as u64;
+            let mut carry = x >> 32;
+            for j in 1..size {
+                let x = t[j] as u64 + m as u64 * self.modulus[j] as u64 + carry;
+                t[j - 1] = x as u32;
+                carry = x >> 32;
+            }
+            let x = t[size] as u64 + carry;
+            t[size - 1] = x as u32;
+            t[size] = t[size + 1] + (x >> 32) as u32;
+            t[size + 1] = 0;
+        }
+
+        t.truncate(size + 1);
+        if cmp_limbs_padded(&t, &self.modulus) != Ordering::Less {
+            t = sub_limbs(&t, &self.modulus);
+        }
+        pad_limbs(&t, size)
+    }
+
+    fn modpow(&self, base: &BigUint, exponent: &BigUint) -> BigUint {
+        let size = self.modulus.len();
+        let modulus = BigUint { limbs: self.modulus.clone() };
+        let base = pad_limbs(&(base % &modulus).limbs, size);
+        let base = self.multiply(&base, &self.r_squared);
+
+        let mut one = vec!(0; size);
+        one[0] = 1;
+        let mut output = self.multiply(&one, &self.r_squared);
+        for index in (0..exponent.bits()).rev() {
+            output = self.multiply(&output, &output);
+            if exponent.bit(index) {
+                output = self.multiply(&output, &base);
+            }
+        }
+        BigUint::from_limbs(self.multiply(&output, &one))
+    }
+}
+
+fn normalize(limbs: &mut Vec<u32>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+fn pad_limbs(limbs: &[u32], size: usize) -> Vec<u32> {
+    let mut output = limbs.to_vec();
+    output.resize(size, 0);
+    output
+}
+
+fn cmp_limbs(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+/// Compares limbs that may have leading zeros.
+fn cmp_limbs_padded(a: &[u32], b: &[u32]) -> Ordering {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    normalize(&mut a);
+    normalize(&mut b);
+    cmp_limbs(&a, &b)
+}
+
+fn add_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut output = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for i in 0..long.len() {
+        let x = long[i] as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+        output.push(x as u32);
+        carry = x >> 32;
+    }
+    if carry > 0 {
+        output.push(carry as u32);
+    }
+    output
+}
+
+/// Subtracts b from a. a must be at least as large as b.
+fn sub_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut output = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for i in 0..a.len() {
+        let x = a[i] as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        output.push(x as u32);
+        borrow = if x < 0 { 1 } else { 0 };
+    }
+    output
+}
+
+/// Adds `value << (32 * shift)` into `target`, growing it as needed.
+fn add_shifted(target: &mut Vec<u32>, value: &[u32], shift: usize) {
+    if target.len() < value.len() + shift {
+        target.resize(value.len() + shift, 0);
+    }
+    let mut carry = 0u64;
+    let mut i = 0;
+    while i < value.len() || carry > 0 {
+        if i + shift == target.len() {
+            target.push(0);
+        }
+        let x = target[i + shift] as u64 + *value.get(i).unwrap_or(&0) as u64 + carry;
+        target[i + shift] = x as u32;
+        carry = x >> 32;
+        i += 1;
+    }
+}
+
+fn mul_schoolbook(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut output = vec!(0u32; a.len() + b.len());
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let z = output[i + j] as u64 + x as u64 * y as u64 + carry;
+            output[i + j] = z as u32;
+            carry = z >> 32;
+        }
+        output[i + b.len()] = carry as u32;
+    }
+    output
+}
+
+/// Multiplies using Karatsuba's method, which splits each number in half
+/// and gets by with three half-size multiplications instead of four.
+fn mul_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return vec!();
+    }
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return mul_schoolbook(a, b);
+    }
+
+    let half = a.len().max(b.len()) / 2;
+    let (a_low, a_high) = split_limbs(a, half);
+    let (b_low, b_high) = split_limbs(b, half);
+
+    let low = mul_limbs(&a_low, &b_low);
+    let high = mul_limbs(&a_high, &b_high);
+    let middle = mul_limbs(&add_limbs(&a_low, &a_high), &add_limbs(&b_low, &b_high));
+    let mut middle = sub_limbs(&middle, &low);
+    normalize(&mut middle);
+    let mut middle = sub_limbs(&middle, &high);
+    normalize(&mut middle);
+
+    let mut output = low;
+    add_shifted(&mut output, &middle, half);
+    add_shifted(&mut output, &high, 2 * half);
+    normalize(&mut output);
+    output
+}
+
+/// Splits limbs into the lowest `at` limbs and the rest, both normalized.
+fn split_limbs(limbs: &[u32], at: usize) -> (Vec<u32>, Vec<u32>) {
+    let at = at.min(limbs.len());
+    let mut low = limbs[..at].to_vec();
+    normalize(&mut low);
+    (low, limbs[at..].to_vec())
+}
+
+fn shl_bits(limbs: &[u32], shift: u32) -> Vec<u32> {
+    if shift == 0 {
+        return limbs.to_vec();
+    }
+    let mut output = Vec::with_capacity(limbs.len() + 1);
+    let mut carry = 0;
+    for &limb in limbs {
+        output.push((limb << shift) | carry);
+        carry = limb >> (32 - shift);
+    }
+    output.push(carry);
+    output
+}
+
+fn shr_bits(limbs: &[u32], shift: u32) -> Vec<u32> {
+    if shift == 0 {
+        return limbs.to_vec();
+    }
+    let mut output = Vec::with_capacity(limbs.len());
+    for i in 0..limbs.len() {
+        let high = match limbs.get(i + 1) {
+            Some(&x) => x << (32 - shift),
+            None => 0,
+        };
+        output.push((limbs[i] >> shift) | high);
+    }
+    output
+}
+
+/// Computes limbs * multiplier + addend in place.
+fn mul_add_small(limbs: &mut Vec<u32>, multiplier: u32, addend: u32) {
+    let mut carry = addend as u64;
+    for limb in limbs.iter_mut() {
+        let x = *limb as u64 * multiplier as u64 + carry;
+        *limb = x as u32;
+        carry = x >> 32;
+    }
+    if carry > 0 {
+        limbs.push(carry as u32);
+    }
+}
+
+/// Divides limbs by a single limb in place, returning the remainder.
+fn div_rem_small(limbs: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut remainder = 0u64;
+    for limb in limbs.iter_mut().rev() {
+        let x = (remainder << 32) | *limb as u64;
+        *limb = (x / divisor as u64) as u32;
+        remainder = x % divisor as u64;
+    }
+    normalize(limbs);
+    remainder as u32
+}
+
+/// Long division, following Knuth's Algorithm D (TAOCP vol. 2, 4.3.1).
+/// `u` must be at least as large as `v`, and `v` must be nonzero.
+fn div_rem_limbs(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if v.len() == 1 {
+        let mut quotient = u.to_vec();
+        let remainder = div_rem_small(&mut quotient, v[0]);
+        return (quotient, vec!(remainder));
+    }
+
+    // shift so the divisor's top bit is set, which keeps the quotient
+    // digit estimates within two of the real value
+    let shift = v[v.len() - 1].leading_zeros();
+    let vn = shl_bits(v, shift);
+    let vn = &vn[..v.len()];
+    let mut un = shl_bits(u, shift);
+    if un.len() == u.len() {
+        un.push(0);
+    }
+
+    let n = vn.len();
+    let m = u.len() - n;
+    let base = 1u64 << 32;
+    let mut quotient = vec!(0u32; m + 1);
+    for j in (0..m + 1).rev() {
+        let numerator = ((un[j + n] as u64) << 32) | un[j + n - 1] as u64;
+        let mut estimate = numerator / vn[n - 1] as u64;
+        let mut remainder = numerator % vn[n - 1] as u64;
+        while estimate >= base
+            || estimate * vn[n - 2] as u64 > ((remainder << 32) | un[j + n - 2] as u64) {
+            estimate -= 1;
+            remainder += vn[n - 1] as u64;
+            if remainder >= base {
+                break;
+            }
+        }
+
+        // subtract estimate * vn from the current window of un
+        let mut borrow = 0i64;
+        for i in 0..n {
+            let product = estimate * vn[i] as u64;
+            let x = un[i + j] as i64 - borrow - (product & 0xffffffff) as i64;
+            un[i + j] = x as u32;
+            borrow = (product >> 32) as i64 - (x >> 32);
+        }
+        let x = un[j + n] as i64 - borrow;
+        un[j + n] = x as u32;
+
+        // the estimate was one too big, so add the divisor back
+        quotient[j] = estimate as u32;
+        if x < 0 {
+            quotient[j] = quotient[j].wrapping_sub(1);
+            let mut carry = 0u64;
+            for i in 0..n {
+                let x = un[i + j] as u64 + vn[i] as u64 + carry;
+                un[i + j] = x as u32;
+                carry = x >> 32;
+            }
+            un[j + n] = un[j + n].wrapping_add(carry as u32);
+        }
+    }
+
+    let mut remainder = shr_bits(&un[..n], shift);
+    normalize(&mut remainder);
+    normalize(&mut quotient);
+    (quotient, remainder)
+}
+
+#[cfg(test)]
+mod test {
+    use super::BigUint;
+    use rng::Rng;
+    use rng::mt19937::Mt19937;
+
+    fn random(rng: &mut Mt19937, bytes: usize) -> BigUint {
+        let mut buffer = vec!(0; bytes);
+        rng.fill_bytes(&mut buffer);
+        BigUint::from_bytes_be(&buffer)
+    }
+
+    #[test]
+    fn hex_and_bytes() {
+        let number = BigUint::from_hex("00000123456789abcdef0011223344");
+        assert_eq!(number.to_hex(), "123456789abcdef0011223344");
+        assert_eq!(BigUint::from_bytes_be(&number.to_bytes_be()), number);
+        assert_eq!(BigUint::zero().to_hex(), "0");
+        assert_eq!(BigUint::zero().to_bytes_be(), vec!(0));
+    }
+
+    #[test]
+    fn decimal() {
+        let text = "123456789012345678901234567890123456789";
+        let number = BigUint::from_decimal(text);
+        assert_eq!(number.to_string(), text);
+        assert_eq!(BigUint::from_decimal("1000000000000000000").to_string(), "1000000000000000000");
+        assert_eq!(BigUint::zero().to_string(), "0");
+    }
+
+    #[test]
+    fn arithmetic_matches_u64() {
+        let mut rng = Mt19937::new(1);
+        for _ in 0..1000 {
+            let a = rng.next_u32() as u64;
+            let b = (rng.next_u32() >> (rng.next_u32() % 32)) as u64 + 1;
+            let (big_a, big_b) = (BigUint::from(a), BigUint::from(b));
+            assert_eq!(&big_a + &big_b, BigUint::from(a + b));
+            assert_eq!(&big_a * &big_b, BigUint::from(a * b));
+            assert_eq!(&big_a / &big_b, BigUint::from(a / b));
+            assert_eq!(&big_a % &big_b, BigUint::from(a % b));
+            if a >= b {
+                assert_eq!(&big_a - &big_b, BigUint::from(a - b));
+            }
+        }
+    }
+
+    #[test]
+    fn karatsuba_matches_schoolbook() {
+        let mut rng = Mt19937::new(2);
+        for &(x, y) in [(200, 200), (512, 130), (130, 512), (1000, 999)].iter() {
+            let a = random(&mut rng, x);
+            let b = random(&mut rng, y);
+            let expected = BigUint::from_limbs(super::mul_schoolbook(&a.limbs, &b.limbs));
+            assert_eq!(&a * &b, expected);
+        }
+    }
+
+    #[test]
+    fn division_identity() {
+        let mut rng = Mt19937::new(3);
+        for &(x, y) in [(64, 8), (64, 32), (100, 99), (256, 128), (33, 5)].iter() {
+            let a = random(&mut rng, x);
+            let b = random(&mut rng, y);
+            let (quotient, remainder) = a.div_rem(&b);
+            assert!(remainder < b);
+            assert_eq!(&(&quotient * &b) + &remainder, a);
+        }
+    }
+
+    #[test]
+    fn division_edge_cases() {
+        // quotient digits that need the add back step
+        let a = BigUint::from_hex("7fffffff800000010000000000000000");
+        let b = BigUint::from_hex("800000008000000200000005");
+        let (quotient, remainder) = a.div_rem(&b);
+        assert_eq!(&(&quotient * &b) + &remainder, a);
+        assert!(remainder < b);
+
+        let c = BigUint::from_hex("ffffffffffffffffffffffff");
+        assert_eq!(&c / &c, BigUint::one());
+        assert_eq!(&c % &c, BigUint::zero());
+    }
+
+    #[test]
+    fn shifts() {
+        let number = BigUint::from_hex("123456789abcdef");
+        assert_eq!((&number << 68).to_hex(), "123456789abcdef00000000000000000");
+        assert_eq!(&(&number << 68) >> 68, number);
+        assert_eq!(&number >> 200, BigUint::zero());
+    }
+
+    #[test]
+    fn montgomery_matches_plain() {
+        let mut rng = Mt19937::new(4);
+        for &size in [8, 64, 128].iter() {
+            let modulus = random(&mut rng, size) + BigUint::one();
+            let modulus = if modulus.is_odd() { modulus } else { modulus + BigUint::one() };
+            let base = random(&mut rng, size + 3);
+            let exponent = random(&mut rng, 16);
+
+            let mut expected = BigUint::one();
+            for index in (0..exponent.bits()).rev() {
+                expected = &(&expected * &expected) % &modulus;
+                if exponent.bit(index) {
+                    expected = &(&expected * &base) % &modulus;
+                }
+            }
+            assert_eq!(base.modpow(&exponent, &modulus), expected);
+        }
+    }
+
+    #[test]
+    fn fermat() {
+        // 2^127 - 1 is prime
+        let p = (BigUint::one() << 127) - BigUint::one();
+        let a = BigUint::from_hex("deadbeefcafebabe0123456789");
+        assert_eq!(a.modpow(&(&p - &BigUint::one()), &p), BigUint::one());
+        let inverse = a.mod_inverse(&p).unwrap();
+        assert_eq!(&(&a * &inverse) % &p, BigUint::one());
+    }
+
+    #[test]
+    fn roots() {
+        let mut rng = Mt19937::new(5);
+        for &n in [2, 3, 5].iter() {
+            let root = random(&mut rng, 40);
+            let power = root.pow(n);
+            assert_eq!(power.nth_root(n), root);
+            assert_eq!((&power - &BigUint::one()).nth_root(n), &root - &BigUint::one());
+            assert_eq!((&power + &BigUint::one()).nth_root(n), root);
+        }
+    }
+}
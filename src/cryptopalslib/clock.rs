@@ -0,0 +1,98 @@
+use std::cell::Cell;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const NANOSECONDS_PER_SECOND: u64 = 1_000_000_000;
+
+/// A source of the current time, since the Unix epoch.
+///
+/// `now` and `sleep` work in whole seconds, which is what seeding things
+/// from the time needs. `now_nanos` and `sleep_nanos` are for timing short
+/// operations. Code takes a `Clock` so that tests can use a
+/// `SimulatedClock` instead of actually waiting.
+pub trait Clock {
+    /// Returns the current time, in seconds.
+    fn now(&self) -> u64;
+
+    /// Waits for the given number of seconds.
+    fn sleep(&self, seconds: u64);
+
+    /// Returns the current time, in nanoseconds.
+    fn now_nanos(&self) -> u64;
+
+    /// Waits for the given number of nanoseconds.
+    fn sleep_nanos(&self, nanoseconds: u64);
+}
+
+/// The real clock.
+pub struct SystemClock;
+
+impl SystemClock {
+    fn since_epoch() -> Duration {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration,
+            Err(_) => panic!("System clock is set before the Unix epoch"),
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemClock::since_epoch().as_secs()
+    }
+
+    fn sleep(&self, seconds: u64) {
+        thread::sleep(Duration::from_secs(seconds));
+    }
+
+    fn now_nanos(&self) -> u64 {
+        let duration = SystemClock::since_epoch();
+        duration.as_secs() * NANOSECONDS_PER_SECOND + duration.subsec_nanos() as u64
+    }
+
+    fn sleep_nanos(&self, nanoseconds: u64) {
+        thread::sleep(Duration::from_nanos(nanoseconds));
+    }
+}
+
+/// A clock that only moves when told to. Sleeping returns immediately.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::clock::{Clock, SimulatedClock};
+///
+/// let clock = SimulatedClock::new(1000);
+/// clock.sleep(60);
+/// assert_eq!(clock.now(), 1060);
+/// clock.sleep_nanos(1500);
+/// assert_eq!(clock.now_nanos(), 1060_000_001_500);
+/// ```
+pub struct SimulatedClock {
+    nanoseconds: Cell<u64>,
+}
+
+impl SimulatedClock {
+    /// Creates a clock starting at the given time, in seconds.
+    pub fn new(start: u64) -> SimulatedClock {
+        SimulatedClock { nanoseconds: Cell::new(start * NANOSECONDS_PER_SECOND) }
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now(&self) -> u64 {
+        self.nanoseconds.get() / NANOSECONDS_PER_SECOND
+    }
+
+    fn sleep(&self, seconds: u64) {
+        self.sleep_nanos(seconds * NANOSECONDS_PER_SECOND);
+    }
+
+    fn now_nanos(&self) -> u64 {
+        self.nanoseconds.get()
+    }
+
+    fn sleep_nanos(&self, nanoseconds: u64) {
+        self.nanoseconds.set(self.nanoseconds.get() + nanoseconds);
+    }
+}
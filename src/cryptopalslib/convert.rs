@@ -57,7 +57,9 @@ pub fn hex_to_base64(hex_string: &str) -> String {
 
     }
 
-    if string_group != 0 {
+    // check the shift rather than the group, which is zero when the
+    // leftover bytes are
+    if shift != 20 {
         // assuming an even number of hex chars, shift will either be 12 or 4.
         // if 12, we have 1 significant byte, and if 4 we have 2.
         let significant_bytes: usize = (2 - (shift / 8)) as usize;
@@ -349,6 +351,12 @@ mod test {
         assert_eq!(output, "//8=");
     }
 
+    #[test]
+    fn trailing_zeros_to_base64() {
+        assert_eq!(super::hex_to_base64("00"), "AA==");
+        assert_eq!(super::hex_to_base64("4d616e00"), "TWFuAA==");
+    }
+
     #[test]
     fn uppercase_to_base64() {
         let output = super::hex_to_base64("4D616E");
@@ -0,0 +1,70 @@
+/// Encodes fields as `key=value&key=value`, like a profile cookie or a query
+/// string. Any `&` and `=` in keys and values are dropped, so a value can't
+/// sneak in fields of its own.
+///
+/// # Examples
+///
+/// ```
+/// let output = cryptopalslib::cookie::encode(&[("email", "foo@bar.com&role=admin"), ("uid", "10")]);
+/// assert_eq!(output, "email=foo@bar.comroleadmin&uid=10");
+/// ```
+pub fn encode(fields: &[(&str, &str)]) -> String {
+    let clean = |x: &str| x.chars().filter(|&c| c != '&' && c != '=').collect::<String>();
+    fields.iter()
+        .map(|&(key, value)| format!("{}={}", clean(key), clean(value)))
+        .collect::<Vec<String>>()
+        .join("&")
+}
+
+/// Parses `key=value&key=value` into its fields, in order. Each field is
+/// split at its first `=`, and one without any gets an empty value. Empty
+/// fields are skipped, and bytes that aren't UTF-8 are replaced, so garbage
+/// in one field doesn't stop the others being read.
+///
+/// # Examples
+///
+/// ```
+/// let fields = cryptopalslib::cookie::parse(b"foo=bar&baz=qux&zap=zazzle");
+/// assert_eq!(fields[1], ("baz".to_string(), "qux".to_string()));
+/// assert_eq!(cryptopalslib::cookie::get(&fields, "zap"), Some("zazzle"));
+/// ```
+pub fn parse(input: &[u8]) -> Vec<(String, String)> {
+    input.split(|&x| x == b'&')
+        .filter(|field| !field.is_empty())
+        .map(|field| {
+            let (key, value) = match field.iter().position(|&x| x == b'=') {
+                Some(index) => (&field[..index], &field[index + 1..]),
+                None => (field, &field[field.len()..]),
+            };
+            (String::from_utf8_lossy(key).into_owned(), String::from_utf8_lossy(value).into_owned())
+        })
+        .collect()
+}
+
+/// Looks up the first field with the given key.
+pub fn get<'a>(fields: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    fields.iter().find(|(x, _)| x == key).map(|(_, value)| value.as_str())
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn round_trip() {
+        let encoded = super::encode(&[("from", "1"), ("to", "2"), ("amount", "100")]);
+        let fields = super::parse(encoded.as_bytes());
+        assert_eq!(fields.len(), 3);
+        assert_eq!(super::get(&fields, "to"), Some("2"));
+        assert_eq!(super::get(&fields, "amount"), Some("100"));
+        assert_eq!(super::get(&fields, "missing"), None);
+    }
+
+    #[test]
+    fn odd_fields() {
+        let fields = super::parse(b"a=1=2&&flag&\xff\xfe=x&a=3");
+        assert_eq!(fields[0], ("a".to_string(), "1=2".to_string()));
+        assert_eq!(fields[1], ("flag".to_string(), "".to_string()));
+        assert_eq!(fields[2].1, "x");
+        assert_eq!(super::get(&fields, "a"), Some("1=2"));
+    }
+}
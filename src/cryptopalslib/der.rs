@@ -0,0 +1,231 @@
+use bignum::BigUint;
+use error::Error;
+
+pub const INTEGER: u8 = 0x02;
+pub const BIT_STRING: u8 = 0x03;
+pub const OCTET_STRING: u8 = 0x04;
+pub const NULL: u8 = 0x05;
+pub const OBJECT_IDENTIFIER: u8 = 0x06;
+pub const SEQUENCE: u8 = 0x30;
+
+/// Encodes a value with the given tag, using the shortest length encoding
+/// as DER requires.
+///
+/// # Examples
+///
+/// ```
+/// let output = cryptopalslib::der::encode(cryptopalslib::der::OCTET_STRING, &[1, 2]);
+/// assert_eq!(output, vec!(4, 2, 1, 2));
+/// ```
+pub fn encode(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let mut output = vec!(tag);
+    let length = contents.len();
+    if length < 0x80 {
+        output.push(length as u8);
+    } else {
+        let bytes = BigUint::from(length as u64).to_bytes_be();
+        output.push(0x80 | bytes.len() as u8);
+        output.extend_from_slice(&bytes);
+    }
+    output.extend_from_slice(contents);
+    output
+}
+
+/// Encodes a non-negative INTEGER. A zero byte is added in front when the
+/// top bit is set, so the value isn't read as negative.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::bignum::BigUint;
+///
+/// assert_eq!(cryptopalslib::der::encode_integer(&BigUint::from(0x80)), vec!(2, 2, 0, 0x80));
+/// assert_eq!(cryptopalslib::der::encode_integer(&BigUint::zero()), vec!(2, 1, 0));
+/// ```
+pub fn encode_integer(value: &BigUint) -> Vec<u8> {
+    let mut bytes = value.to_bytes_be();
+    if bytes[0] & 0x80 != 0 {
+        bytes.insert(0, 0);
+    }
+    encode(INTEGER, &bytes)
+}
+
+/// Encodes a SEQUENCE of already encoded values.
+pub fn encode_sequence(items: &[Vec<u8>]) -> Vec<u8> {
+    encode(SEQUENCE, &items.concat())
+}
+
+/// Reads DER values one after another from a buffer.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::bignum::BigUint;
+/// use cryptopalslib::der::Reader;
+///
+/// let mut reader = Reader::new(&[0x30, 3, 2, 1, 5]);
+/// let mut sequence = reader.read_sequence().unwrap();
+/// assert_eq!(sequence.read_integer(), Ok(BigUint::from(5)));
+/// assert!(sequence.finish().is_ok());
+/// assert!(reader.finish().is_ok());
+/// ```
+pub struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a [u8]) -> Reader<'a> {
+        Reader { data }
+    }
+
+    /// Whether everything has been read.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns an error if there's anything left to read.
+    pub fn finish(&self) -> Result<(), Error> {
+        match self.is_empty() {
+            true => Ok(()),
+            false => Err(Error::InvalidEncoding),
+        }
+    }
+
+    /// Reads a value with the given tag, and returns its contents.
+    pub fn read(&mut self, tag: u8) -> Result<&'a [u8], Error> {
+        if self.data.len() < 2 || self.data[0] != tag {
+            return Err(Error::InvalidEncoding);
+        }
+
+        let (length, header) = match self.data[1] {
+            x if x < 0x80 => (x as usize, 2),
+            // indefinite lengths aren't allowed in DER, and anything longer
+            // than four bytes is absurd here
+            x if x == 0x80 || x > 0x84 => return Err(Error::InvalidEncoding),
+            x => {
+                let count = (x & 0x7f) as usize;
+                if self.data.len() < 2 + count || self.data[2] == 0 {
+                    return Err(Error::InvalidEncoding);
+                }
+                let length = self.data[2..2 + count].iter().fold(0, |x, &y| (x << 8) | y as usize);
+                if length < 0x80 {
+                    return Err(Error::InvalidEncoding);
+                }
+                (length, 2 + count)
+            },
+        };
+
+        if self.data.len() < header + length {
+            return Err(Error::InvalidEncoding);
+        }
+        let contents = &self.data[header..header + length];
+        self.data = &self.data[header + length..];
+        Ok(contents)
+    }
+
+    /// Reads a non-negative INTEGER.
+    pub fn read_integer(&mut self) -> Result<BigUint, Error> {
+        let contents = self.read(INTEGER)?;
+        let negative = contents.first().map_or(true, |&x| x & 0x80 != 0);
+        let padded = contents.len() > 1 && contents[0] == 0 && contents[1] & 0x80 == 0;
+        if negative || padded {
+            return Err(Error::InvalidEncoding);
+        }
+        Ok(BigUint::from_bytes_be(contents))
+    }
+
+    /// Reads a SEQUENCE, returning a reader over its contents.
+    pub fn read_sequence(&mut self) -> Result<Reader<'a>, Error> {
+        self.read(SEQUENCE).map(Reader::new)
+    }
+}
+
+/// Wraps DER data in PEM armor with the given label, like
+/// `-----BEGIN RSA PUBLIC KEY-----`.
+pub fn to_pem(label: &str, der: &[u8]) -> String {
+    let base64 = ::convert::hex_to_base64(&::convert::decimals_to_hex_string(der.to_vec()));
+    let mut output = format!("-----BEGIN {}-----\n", label);
+    for line in base64.as_bytes().chunks(64) {
+        output.push_str(&String::from_utf8_lossy(line));
+        output.push('\n');
+    }
+    output.push_str(&format!("-----END {}-----\n", label));
+    output
+}
+
+/// Pulls the DER data out of PEM armor with the given label. Anything
+/// before the first line or after the last is ignored.
+///
+/// # Examples
+///
+/// ```
+/// let pem = cryptopalslib::der::to_pem("TEST", &[1, 2, 3]);
+/// assert_eq!(pem, "-----BEGIN TEST-----\nAQID\n-----END TEST-----\n");
+/// assert_eq!(cryptopalslib::der::from_pem("TEST", &pem), Ok(vec!(1, 2, 3)));
+/// ```
+pub fn from_pem(label: &str, pem: &str) -> Result<Vec<u8>, Error> {
+    let begin = format!("-----BEGIN {}-----", label);
+    let end = format!("-----END {}-----", label);
+    let mut lines = pem.lines().map(|x| x.trim()).skip_while(|&x| x != begin);
+    if lines.next().is_none() {
+        return Err(Error::InvalidEncoding);
+    }
+
+    let mut base64 = String::new();
+    let mut ended = false;
+    for line in lines {
+        if line == end {
+            ended = true;
+            break;
+        }
+        base64.push_str(line);
+    }
+
+    // check the base64 here, since the converter panics on bad input
+    let valid = base64.chars().all(|x| x.is_ascii_alphanumeric() || x == '+' || x == '/' || x == '=');
+    let padding = base64.len() - base64.trim_end_matches('=').len();
+    if !ended || base64.is_empty() || base64.len() % 4 != 0 || !valid || padding > 2 || base64.trim_end_matches('=').contains('=') {
+        return Err(Error::InvalidEncoding);
+    }
+    Ok(::convert::hex_string_to_decimal_pairs(&::convert::base64_to_hex(&base64)))
+}
+
+#[cfg(test)]
+mod test {
+    use bignum::BigUint;
+    use error::Error;
+    use super::Reader;
+
+    #[test]
+    fn long_lengths() {
+        let contents = vec!(7; 300);
+        let encoded = super::encode(super::OCTET_STRING, &contents);
+        assert_eq!(encoded[..4], [4, 0x82, 1, 44]);
+        let mut reader = Reader::new(&encoded);
+        assert_eq!(reader.read(super::OCTET_STRING), Ok(&contents[..]));
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn integers_roundtrip() {
+        for value in [0u64, 1, 127, 128, 255, 256, 0xffffffffffff].iter() {
+            let value = BigUint::from(*value);
+            let encoded = super::encode_integer(&value);
+            assert_eq!(Reader::new(&encoded).read_integer(), Ok(value));
+        }
+    }
+
+    #[test]
+    fn rejects_bad_encodings() {
+        // truncated, negative, padded, non-minimal length, wrong tag
+        for bad in [&[2, 2, 1][..], &[2, 1, 0x80], &[2, 2, 0, 1], &[4, 0x81, 1, 0], &[4, 1, 0]].iter() {
+            assert_eq!(Reader::new(bad).read_integer(), Err(Error::InvalidEncoding));
+        }
+        for bad in ["A!==", "A===", "===="].iter() {
+            let pem = format!("-----BEGIN TEST-----\n{}\n-----END TEST-----", bad);
+            assert_eq!(super::from_pem("TEST", &pem), Err(Error::InvalidEncoding));
+        }
+        assert_eq!(super::from_pem("TEST", "-----BEGIN TEST-----\nAQID\n"), Err(Error::InvalidEncoding));
+        assert_eq!(super::from_pem("OTHER", &super::to_pem("TEST", &[1])), Err(Error::InvalidEncoding));
+    }
+}
@@ -0,0 +1,228 @@
+use bignum::BigUint;
+use error::Error;
+use hash::MerkleDamgard;
+use hash::sha1::Sha1;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// The 1536-bit MODP prime from RFC 3526, which is also the one given in
+/// the challenges.
+static MODP_1536_PRIME: &'static str = "\
+    ffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74\
+    020bbea63b139b22514a08798e3404ddef9519b3cd3a431b302b0a6df25f1437\
+    4fe1356d6d51c245e485b576625e7ec6f44c42e9a637ed6b0bff5cb6f406b7ed\
+    ee386bfb5a899fa5ae9f24117c4b1fe649286651ece45b3dc2007cb8a163bf05\
+    98da48361c55d39a69163fa8fd24cf5f83655d23dca3ad961c62f356208552bb\
+    9ed529077096966d670c354e4abc9804f1746c08ca237327ffffffffffffffff";
+
+/// A Diffie-Hellman group: a prime modulus and a generator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    pub p: BigUint,
+    pub g: BigUint,
+}
+
+/// A Diffie-Hellman key pair. `public` is g ^ `private` mod p.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyPair {
+    pub private: BigUint,
+    pub public: BigUint,
+}
+
+impl Group {
+    /// Creates a group from any prime and generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is less than 3, which leaves no room for a private key.
+    pub fn new(p: BigUint, g: BigUint) -> Group {
+        if p < BigUint::from(3) {
+            panic!("Modulus is too small");
+        }
+        Group { p, g }
+    }
+
+    /// The 1536-bit MODP group from RFC 3526, with generator 2.
+    pub fn modp_1536() -> Group {
+        Group::new(BigUint::from_hex(MODP_1536_PRIME), BigUint::from(2))
+    }
+
+    /// Generates a key pair with a random private key in [1, p - 1).
+    pub fn generate_keypair(&self) -> KeyPair {
+        let bound = &self.p - &BigUint::from(2);
+        self.keypair_from_private(BigUint::random_below(&bound) + BigUint::one())
+    }
+
+    /// Computes the public key that goes with a private key.
+    pub fn keypair_from_private(&self, private: BigUint) -> KeyPair {
+        let public = self.g.modpow(&private, &self.p);
+        KeyPair { private, public }
+    }
+
+    /// Combines our key pair with the other side's public key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cryptopalslib::bignum::BigUint;
+    /// use cryptopalslib::dh::Group;
+    ///
+    /// let group = Group::new(BigUint::from(37), BigUint::from(5));
+    /// let alice = group.generate_keypair();
+    /// let bob = group.generate_keypair();
+    /// assert_eq!(group.shared_secret(&alice, &bob.public), group.shared_secret(&bob, &alice.public));
+    /// ```
+    pub fn shared_secret(&self, keys: &KeyPair, public: &BigUint) -> BigUint {
+        public.modpow(&keys.private, &self.p)
+    }
+}
+
+/// Derives a 16 byte AES key from a shared secret, by taking the first 16
+/// bytes of the SHA-1 of its big-endian bytes.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::bignum::BigUint;
+///
+/// let key = cryptopalslib::dh::derive_key(&BigUint::zero());
+/// assert_eq!(cryptopalslib::convert::decimals_to_hex_string(key), "5ba93c9db0cff93f52b521d7420e43f6");
+/// ```
+pub fn derive_key(secret: &BigUint) -> Vec<u8> {
+    Sha1::digest(&secret.to_bytes_be())[..16].to_vec()
+}
+
+/// The messages exchanged by Diffie-Hellman based protocols.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    /// Proposes a group, without a public key.
+    Negotiate(Group),
+    /// Accepts a proposed group.
+    Ack,
+    /// Proposes a group along with the sender's public key.
+    Handshake(Group, BigUint),
+    /// A public key on its own.
+    PublicKey(BigUint),
+    /// Data encrypted under the session key.
+    Data { ciphertext: Vec<u8>, iv: Vec<u8> },
+}
+
+/// One end of an in-memory, bidirectional message channel.
+///
+/// Endpoints can be handed to different threads. Anything sent on one end
+/// of a pair is received, in order, on the other. Protocol attacks sit in
+/// the middle by holding one end of each of two pairs.
+pub struct Endpoint<T> {
+    sender: mpsc::Sender<T>,
+    receiver: mpsc::Receiver<T>,
+}
+
+impl<T> Endpoint<T> {
+    /// Sends a message to the other end.
+    pub fn send(&self, message: T) -> Result<(), Error> {
+        self.sender.send(message).map_err(|_| Error::ChannelClosed)
+    }
+
+    /// Waits for a message from the other end.
+    pub fn recv(&self) -> Result<T, Error> {
+        self.receiver.recv().map_err(|_| Error::ChannelClosed)
+    }
+
+    /// Waits up to `timeout` for a message from the other end, returning
+    /// `None` if nothing arrived in time.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<Option<T>, Error> {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(message) => Ok(Some(message)),
+            Err(mpsc::RecvTimeoutError::Timeout) => Ok(None),
+            Err(mpsc::RecvTimeoutError::Disconnected) => Err(Error::ChannelClosed),
+        }
+    }
+}
+
+/// Creates a connected pair of endpoints.
+///
+/// # Examples
+///
+/// ```
+/// let (first, second) = cryptopalslib::dh::channel();
+/// first.send("hello").unwrap();
+/// assert_eq!(second.recv(), Ok("hello"));
+/// ```
+pub fn channel<T>() -> (Endpoint<T>, Endpoint<T>) {
+    let (first_sender, first_receiver) = mpsc::channel();
+    let (second_sender, second_receiver) = mpsc::channel();
+    let first = Endpoint { sender: first_sender, receiver: second_receiver };
+    let second = Endpoint { sender: second_sender, receiver: first_receiver };
+    (first, second)
+}
+
+/// Starts a key exchange over `endpoint` in the given group, and returns the
+/// derived session key.
+pub fn initiate(endpoint: &Endpoint<Message>, group: &Group) -> Result<Vec<u8>, Error> {
+    let keys = group.generate_keypair();
+    endpoint.send(Message::Handshake(group.clone(), keys.public.clone()))?;
+    match endpoint.recv()? {
+        Message::PublicKey(public) => Ok(derive_key(&group.shared_secret(&keys, &public))),
+        _ => Err(Error::UnexpectedMessage),
+    }
+}
+
+/// Answers a key exchange started with `initiate`, and returns the derived
+/// session key.
+pub fn respond(endpoint: &Endpoint<Message>) -> Result<Vec<u8>, Error> {
+    match endpoint.recv()? {
+        Message::Handshake(group, public) => {
+            let keys = group.generate_keypair();
+            endpoint.send(Message::PublicKey(keys.public.clone()))?;
+            Ok(derive_key(&group.shared_secret(&keys, &public)))
+        },
+        _ => Err(Error::UnexpectedMessage),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bignum::BigUint;
+    use error::Error;
+    use std::thread;
+    use super::{Group, Message};
+
+    #[test]
+    fn small_group() {
+        let group = Group::new(BigUint::from(37), BigUint::from(5));
+        let alice = group.keypair_from_private(BigUint::from(7));
+        let bob = group.keypair_from_private(BigUint::from(11));
+        assert_eq!(alice.public, BigUint::from(18));
+        assert_eq!(group.shared_secret(&alice, &bob.public), group.shared_secret(&bob, &alice.public));
+    }
+
+    #[test]
+    fn modp_1536() {
+        let group = Group::modp_1536();
+        assert_eq!(group.p.bits(), 1536);
+        let alice = group.generate_keypair();
+        let bob = group.generate_keypair();
+        let secret = group.shared_secret(&alice, &bob.public);
+        assert_eq!(secret, group.shared_secret(&bob, &alice.public));
+        assert!(secret < group.p);
+    }
+
+    #[test]
+    fn exchange_over_channel() {
+        let (alice, bob) = super::channel();
+        let handle = thread::spawn(move || super::respond(&bob));
+        let alice_key = super::initiate(&alice, &Group::modp_1536()).unwrap();
+        let bob_key = handle.join().unwrap().unwrap();
+        assert_eq!(alice_key, bob_key);
+        assert_eq!(alice_key.len(), 16);
+    }
+
+    #[test]
+    fn unexpected_message() {
+        let (alice, bob) = super::channel();
+        alice.send(Message::Ack).unwrap();
+        assert_eq!(super::respond(&bob), Err(Error::UnexpectedMessage));
+        drop(alice);
+        assert_eq!(super::respond(&bob), Err(Error::ChannelClosed));
+    }
+}
@@ -0,0 +1,249 @@
+use bignum::BigUint;
+use rsa::HashAlgorithm;
+
+/// The 1024-bit p from the DSA challenges.
+static CHALLENGE_P: &'static str = "\
+    800000000000000089e1855218a0e7dac38136ffafa72eda7859f2171e25e65e\
+    ac698c1702578b07dc2a1076da241c76c62d374d8389ea5aeffd3226a0530cc5\
+    65f3bf6b50929139ebeac04f48c3c84afb796d61e5a4f9a8fda812ab59494232\
+    c7d2b4deb50aa18ee9e132bfa85ac4374d7f9091abc3d015efc871a584471bb1";
+
+/// The 160-bit q from the DSA challenges.
+static CHALLENGE_Q: &'static str = "f4f47f05794b256174bba6e9b396a7707e563c5b";
+
+/// The generator from the DSA challenges.
+static CHALLENGE_G: &'static str = "\
+    5958c9d3898b224b12672c0b98e06c60df923cb8bc999d119458fef538b8fa40\
+    46c8db53039db620c094c9fa077ef389b5322a559946a71903f990f1f7e0e025\
+    e2d7f7cf494aff1a0470f5b64c36b625a097f1651fe775323556fe00b3608c88\
+    7892878480e99041be601a62166ca6894bdd41a7054ec89f756ba9fc95302291";
+
+/// DSA domain parameters: primes p and q, with q dividing p - 1, and a
+/// generator g of the order q subgroup mod p.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameters {
+    pub p: BigUint,
+    pub q: BigUint,
+    pub g: BigUint,
+}
+
+/// A DSA public key. `y` is g ^ x mod p.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublicKey {
+    pub params: Parameters,
+    pub y: BigUint,
+}
+
+/// A DSA private key, with its public half.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrivateKey {
+    pub params: Parameters,
+    pub x: BigUint,
+    pub y: BigUint,
+}
+
+/// A DSA signature.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    pub r: BigUint,
+    pub s: BigUint,
+}
+
+impl Parameters {
+    /// The parameters given in the challenges: a 1024-bit p and a 160-bit
+    /// q.
+    pub fn challenge() -> Parameters {
+        Parameters {
+            p: BigUint::from_hex(CHALLENGE_P),
+            q: BigUint::from_hex(CHALLENGE_Q),
+            g: BigUint::from_hex(CHALLENGE_G),
+        }
+    }
+
+    /// Generates fresh parameters with an `l_bits` p and an `n_bits` q.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_bits` is less than 16, or `l_bits` isn't larger than
+    /// `n_bits`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cryptopalslib::bignum::BigUint;
+    /// use cryptopalslib::dsa::Parameters;
+    ///
+    /// let params = Parameters::generate(256, 64);
+    /// assert_eq!((params.p.bits(), params.q.bits()), (256, 64));
+    /// assert!(((&params.p - &BigUint::one()) % &params.q).is_zero());
+    /// assert_eq!(params.g.modpow(&params.q, &params.p), BigUint::one());
+    /// ```
+    pub fn generate(l_bits: usize, n_bits: usize) -> Parameters {
+        if n_bits < 16 || l_bits <= n_bits {
+            panic!("Invalid DSA parameter sizes");
+        }
+
+        let q = ::rsa::generate_prime(n_bits);
+        let step = &q << 1;
+        let top = BigUint::one() << (l_bits - 1);
+        let p = loop {
+            // a random l_bits number, moved down to the nearest 1 mod 2q
+            let c = BigUint::random_below(&top) + &top;
+            let p = &(&c - &(&c % &step)) + &BigUint::one();
+            if p.bits() == l_bits && ::rsa::is_probable_prime(&p, 40) {
+                break p;
+            }
+        };
+
+        let one = BigUint::one();
+        let exponent = &(&p - &one) / &q;
+        let mut h = BigUint::from(2);
+        let g = loop {
+            let g = h.modpow(&exponent, &p);
+            if g != one {
+                break g;
+            }
+            h = h + BigUint::one();
+        };
+        Parameters { p, q, g }
+    }
+}
+
+impl Default for Parameters {
+    fn default() -> Parameters {
+        Parameters::challenge()
+    }
+}
+
+/// Converts a message digest to the integer that gets signed: the leftmost
+/// bits of the digest, as many as q has.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::bignum::BigUint;
+///
+/// let q = BigUint::from(0xffff);
+/// assert_eq!(cryptopalslib::dsa::digest_to_integer(&[0x12, 0x34, 0x56], &q), BigUint::from(0x1234));
+/// ```
+pub fn digest_to_integer(digest: &[u8], q: &BigUint) -> BigUint {
+    let z = BigUint::from_bytes_be(digest);
+    let bits = digest.len() * 8;
+    match bits > q.bits() {
+        true => z >> (bits - q.bits()),
+        false => z,
+    }
+}
+
+impl PublicKey {
+    /// Checks a signature on a message.
+    pub fn verify(&self, hash: HashAlgorithm, message: &[u8], signature: &Signature) -> bool {
+        let params = &self.params;
+        let Signature { ref r, ref s } = *signature;
+        if r.is_zero() || *r >= params.q || s.is_zero() || *s >= params.q {
+            return false;
+        }
+
+        let w = match s.mod_inverse(&params.q) {
+            Some(w) => w,
+            None => return false,
+        };
+        let z = digest_to_integer(&hash.hash(message), &params.q);
+        let u1 = &(&z * &w) % &params.q;
+        let u2 = &(r * &w) % &params.q;
+        let v = &(&params.g.modpow(&u1, &params.p) * &self.y.modpow(&u2, &params.p)) % &params.p;
+        &v % &params.q == *r
+    }
+}
+
+impl PrivateKey {
+    /// Generates a key pair with a random x in [1, q).
+    pub fn generate(params: &Parameters) -> PrivateKey {
+        let bound = &params.q - &BigUint::one();
+        PrivateKey::from_x(params, BigUint::random_below(&bound) + BigUint::one())
+    }
+
+    /// Builds the key pair for a given x.
+    pub fn from_x(params: &Parameters, x: BigUint) -> PrivateKey {
+        let y = params.g.modpow(&x, &params.p);
+        PrivateKey { params: params.clone(), x, y }
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey { params: self.params.clone(), y: self.y.clone() }
+    }
+
+    /// Signs a message, with a random nonce.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cryptopalslib::dsa::{Parameters, PrivateKey};
+    /// use cryptopalslib::rsa::HashAlgorithm;
+    ///
+    /// let key = PrivateKey::generate(&Parameters::generate(256, 64));
+    /// let signature = key.sign(HashAlgorithm::Sha256, b"hello");
+    /// assert!(key.public_key().verify(HashAlgorithm::Sha256, b"hello", &signature));
+    /// assert!(!key.public_key().verify(HashAlgorithm::Sha256, b"hellp", &signature));
+    /// ```
+    pub fn sign(&self, hash: HashAlgorithm, message: &[u8]) -> Signature {
+        let bound = &self.params.q - &BigUint::one();
+        loop {
+            let k = BigUint::random_below(&bound) + BigUint::one();
+            if let Some(signature) = self.sign_with_nonce(hash, message, &k) {
+                return signature;
+            }
+        }
+    }
+
+    /// Signs a message with a chosen nonce k. Returns `None` if r or s
+    /// comes out as 0, or k has no inverse mod q, in which case another k
+    /// has to be picked.
+    pub fn sign_with_nonce(&self, hash: HashAlgorithm, message: &[u8], k: &BigUint) -> Option<Signature> {
+        let params = &self.params;
+        let r = &params.g.modpow(k, &params.p) % &params.q;
+        let k_inverse = k.mod_inverse(&params.q)?;
+        let z = digest_to_integer(&hash.hash(message), &params.q);
+        let s = &(&k_inverse * &(&z + &(&self.x * &r))) % &params.q;
+        match r.is_zero() || s.is_zero() {
+            true => None,
+            false => Some(Signature { r, s }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bignum::BigUint;
+    use rsa::HashAlgorithm;
+    use super::{Parameters, PrivateKey, Signature};
+
+    #[test]
+    fn challenge_parameters() {
+        let params = Parameters::challenge();
+        assert!(((&params.p - &BigUint::one()) % &params.q).is_zero());
+        assert_eq!(params.g.modpow(&params.q, &params.p), BigUint::one());
+
+        let key = PrivateKey::generate(&params);
+        let public = key.public_key();
+        for &hash in &[HashAlgorithm::Sha1, HashAlgorithm::Sha256] {
+            let signature = key.sign(hash, b"hello");
+            assert!(public.verify(hash, b"hello", &signature));
+
+            let tampered = Signature { r: signature.r.clone(), s: &signature.s + &BigUint::one() };
+            assert!(!public.verify(hash, b"hello", &tampered));
+            let out_of_range = Signature { r: &signature.r + &params.q, s: signature.s.clone() };
+            assert!(!public.verify(hash, b"hello", &out_of_range));
+        }
+    }
+
+    #[test]
+    fn same_nonce_same_r() {
+        let key = PrivateKey::generate(&Parameters::challenge());
+        let k = BigUint::from(12345);
+        let first = key.sign_with_nonce(HashAlgorithm::Sha1, b"one", &k).unwrap();
+        let second = key.sign_with_nonce(HashAlgorithm::Sha1, b"two", &k).unwrap();
+        assert_eq!(first.r, second.r);
+        assert!(first.s != second.s);
+    }
+}
@@ -0,0 +1,41 @@
+use std::error;
+use std::fmt;
+
+/// Errors returned by the library's decryption, parsing and protocol
+/// functions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// Decrypted data wasn't ASCII. Holds the offending plaintext.
+    NonAscii(Vec<u8>),
+    /// Decrypted data didn't end with valid PKCS#7 padding.
+    InvalidPadding,
+    /// The other end of a message channel went away.
+    ChannelClosed,
+    /// A protocol received a message it wasn't expecting.
+    UnexpectedMessage,
+    /// A login named a user that isn't registered.
+    UnknownUser,
+    /// A public key was rejected as unsafe, e.g. for being 0 mod N.
+    InvalidPublicKey,
+    /// DER or PEM data was malformed, or didn't hold what was expected.
+    InvalidEncoding,
+    /// A message's MAC didn't match.
+    InvalidMac,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::NonAscii(ref plaintext) => write!(f, "plaintext isn't ASCII: {:?}", plaintext),
+            Error::InvalidPadding => write!(f, "invalid PKCS#7 padding"),
+            Error::ChannelClosed => write!(f, "channel closed"),
+            Error::UnexpectedMessage => write!(f, "unexpected message"),
+            Error::UnknownUser => write!(f, "unknown user"),
+            Error::InvalidPublicKey => write!(f, "invalid public key"),
+            Error::InvalidEncoding => write!(f, "invalid DER or PEM encoding"),
+            Error::InvalidMac => write!(f, "invalid MAC"),
+        }
+    }
+}
+
+impl error::Error for Error {}
@@ -0,0 +1,197 @@
+/// A linear equation over GF(2): the XOR of the listed variables equals
+/// `value`. Variables are kept sorted and unique.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equation {
+    pub variables: Vec<usize>,
+    pub value: bool,
+}
+
+impl Equation {
+    /// Creates an equation, cancelling out any variable listed an even
+    /// number of times.
+    pub fn new(variables: Vec<usize>, value: bool) -> Equation {
+        let mut sorted = variables;
+        sorted.sort();
+        let mut deduped: Vec<usize> = vec!();
+        for variable in sorted {
+            if deduped.last() == Some(&variable) {
+                deduped.pop();
+            } else {
+                deduped.push(variable);
+            }
+        }
+        Equation { variables: deduped, value }
+    }
+
+    /// XORs another equation into this one.
+    fn add(&mut self, other: &Equation) {
+        self.variables = xor_sorted(&self.variables, &other.variables);
+        self.value ^= other.value;
+    }
+}
+
+/// XORs two sorted lists of variables, dropping variables found in both.
+///
+/// # Examples
+///
+/// ```
+/// let output = cryptopalslib::gf2::xor_sorted(&[1, 2, 5], &[2, 3]);
+/// assert_eq!(output, vec!(1, 3, 5));
+/// ```
+pub fn xor_sorted(first: &[usize], second: &[usize]) -> Vec<usize> {
+    let mut output = Vec::with_capacity(first.len() + second.len());
+    let mut i = 0;
+    let mut j = 0;
+    while i < first.len() && j < second.len() {
+        if first[i] < second[j] {
+            output.push(first[i]);
+            i += 1;
+        } else if second[j] < first[i] {
+            output.push(second[j]);
+            j += 1;
+        } else {
+            i += 1;
+            j += 1;
+        }
+    }
+    output.extend_from_slice(&first[i..]);
+    output.extend_from_slice(&second[j..]);
+    output
+}
+
+/// Returned when an equation contradicts the ones already in a system.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inconsistent;
+
+/// A system of linear equations over GF(2), kept in echelon form as
+/// equations are added.
+///
+/// Equations are stored sparsely, which keeps things fast when each one
+/// only touches a few variables.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::gf2::{Equation, System};
+///
+/// let mut system = System::new(2);
+/// system.add(Equation::new(vec!(0, 1), true)).unwrap();
+/// system.add(Equation::new(vec!(1), true)).unwrap();
+/// let solution = system.solve();
+/// assert_eq!(solution.values, vec!(false, true));
+/// ```
+pub struct System {
+    // pivots[n] holds the equation whose lowest variable is n, if any
+    pivots: Vec<Option<Equation>>,
+    rank: usize,
+}
+
+/// The result of solving a `System`.
+///
+/// Variables that the system doesn't pin down are set to `false`, and are
+/// marked in `determined`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    pub values: Vec<bool>,
+    pub determined: Vec<bool>,
+}
+
+impl System {
+    /// Creates an empty system over `variables` unknowns.
+    pub fn new(variables: usize) -> System {
+        System { pivots: vec!(None; variables), rank: 0 }
+    }
+
+    /// The number of linearly independent equations added so far.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// Adds an equation to the system.
+    ///
+    /// Returns whether the equation was independent of the ones already in
+    /// the system, or an error if it contradicts them.
+    pub fn add(&mut self, equation: Equation) -> Result<bool, Inconsistent> {
+        let mut equation = equation;
+        loop {
+            let lowest = match equation.variables.first() {
+                Some(&x) => x,
+                None => {
+                    return match equation.value {
+                        true => Err(Inconsistent),
+                        false => Ok(false),
+                    };
+                }
+            };
+            match self.pivots[lowest] {
+                Some(ref pivot) => equation.add(pivot),
+                None => break,
+            }
+        }
+
+        let lowest = equation.variables[0];
+        self.pivots[lowest] = Some(equation);
+        self.rank += 1;
+        Ok(true)
+    }
+
+    /// Solves the system by back substitution.
+    pub fn solve(&self) -> Solution {
+        let count = self.pivots.len();
+        let mut values = vec!(false; count);
+        let mut determined = vec!(false; count);
+
+        // every variable in a pivot equation is higher than the pivot, so
+        // working downwards means they've all been solved already
+        for variable in (0..count).rev() {
+            if let Some(ref equation) = self.pivots[variable] {
+                let mut value = equation.value;
+                let mut known = true;
+                for &other in equation.variables[1..].iter() {
+                    value ^= values[other];
+                    known = known && determined[other];
+                }
+                values[variable] = value;
+                determined[variable] = known;
+            }
+        }
+
+        Solution { values, determined }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Equation, Inconsistent, System};
+
+    #[test]
+    fn equation_cancels_duplicates() {
+        let equation = Equation::new(vec!(3, 1, 3, 2, 3), false);
+        assert_eq!(equation.variables, vec!(1, 2, 3));
+    }
+
+    #[test]
+    fn solve_three_variables() {
+        // x0 ^ x1 = 1, x1 ^ x2 = 0, x0 ^ x2 = 1 (dependent), x2 = 1
+        let mut system = System::new(3);
+        assert_eq!(system.add(Equation::new(vec!(0, 1), true)), Ok(true));
+        assert_eq!(system.add(Equation::new(vec!(1, 2), false)), Ok(true));
+        assert_eq!(system.add(Equation::new(vec!(0, 2), true)), Ok(false));
+        assert_eq!(system.add(Equation::new(vec!(2), true)), Ok(true));
+        assert_eq!(system.rank(), 3);
+
+        let solution = system.solve();
+        assert_eq!(solution.values, vec!(false, true, true));
+        assert_eq!(solution.determined, vec!(true, true, true));
+    }
+
+    #[test]
+    fn inconsistent_and_underdetermined() {
+        let mut system = System::new(3);
+        system.add(Equation::new(vec!(0, 1), true)).unwrap();
+        assert_eq!(system.add(Equation::new(vec!(0, 1), false)), Err(Inconsistent));
+
+        let solution = system.solve();
+        assert_eq!(solution.determined, vec!(false, false, false));
+    }
+}
@@ -0,0 +1,106 @@
+use hash::MerkleDamgard;
+
+/// MD4, as specified in RFC 1320.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::hash::MerkleDamgard;
+/// use cryptopalslib::hash::md4::Md4;
+///
+/// let digest = Md4::digest(b"abc");
+/// assert_eq!(cryptopalslib::convert::decimals_to_hex_string(digest), "a448017aaf21d8525fc10ae87aa6729d");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Md4;
+
+impl MerkleDamgard for Md4 {
+    type State = [u32; 4];
+
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 16;
+    const BIG_ENDIAN_LENGTH: bool = false;
+
+    fn initial_state() -> [u32; 4] {
+        [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476]
+    }
+
+    fn compress(state: &mut [u32; 4], block: &[u8]) {
+        let mut x = [0u32; 16];
+        ::hash::read_le_words(block, &mut x);
+
+        let [mut a, mut b, mut c, mut d] = *state;
+
+        // round 1: F(x, y, z) = (x & y) | (!x & z)
+        for &index in [0, 4, 8, 12].iter() {
+            a = (a.wrapping_add((b & c) | (!b & d)).wrapping_add(x[index])).rotate_left(3);
+            d = (d.wrapping_add((a & b) | (!a & c)).wrapping_add(x[index + 1])).rotate_left(7);
+            c = (c.wrapping_add((d & a) | (!d & b)).wrapping_add(x[index + 2])).rotate_left(11);
+            b = (b.wrapping_add((c & d) | (!c & a)).wrapping_add(x[index + 3])).rotate_left(19);
+        }
+
+        // round 2: G(x, y, z) = (x & y) | (x & z) | (y & z)
+        let g = |x: u32, y: u32, z: u32| (x & y) | (x & z) | (y & z);
+        for &index in [0, 1, 2, 3].iter() {
+            a = (a.wrapping_add(g(b, c, d)).wrapping_add(x[index]).wrapping_add(0x5a827999)).rotate_left(3);
+            d = (d.wrapping_add(g(a, b, c)).wrapping_add(x[index + 4]).wrapping_add(0x5a827999)).rotate_left(5);
+            c = (c.wrapping_add(g(d, a, b)).wrapping_add(x[index + 8]).wrapping_add(0x5a827999)).rotate_left(9);
+            b = (b.wrapping_add(g(c, d, a)).wrapping_add(x[index + 12]).wrapping_add(0x5a827999)).rotate_left(13);
+        }
+
+        // round 3: H(x, y, z) = x ^ y ^ z
+        for &index in [0, 2, 1, 3].iter() {
+            a = (a.wrapping_add(b ^ c ^ d).wrapping_add(x[index]).wrapping_add(0x6ed9eba1)).rotate_left(3);
+            d = (d.wrapping_add(a ^ b ^ c).wrapping_add(x[index + 8]).wrapping_add(0x6ed9eba1)).rotate_left(9);
+            c = (c.wrapping_add(d ^ a ^ b).wrapping_add(x[index + 4]).wrapping_add(0x6ed9eba1)).rotate_left(11);
+            b = (b.wrapping_add(c ^ d ^ a).wrapping_add(x[index + 12]).wrapping_add(0x6ed9eba1)).rotate_left(15);
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+    }
+
+    fn state_to_digest(state: &[u32; 4]) -> Vec<u8> {
+        ::hash::write_le_words(state)
+    }
+
+    fn digest_to_state(digest: &[u8]) -> [u32; 4] {
+        if digest.len() != 16 {
+            panic!("MD4 digests are 16 bytes long.");
+        }
+        let mut state = [0; 4];
+        ::hash::read_le_words(digest, &mut state);
+        state
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use convert::decimals_to_hex_string;
+    use hash::MerkleDamgard;
+
+    #[test]
+    fn rfc_1320_vectors() {
+        let vectors: Vec<(&[u8], &str)> = vec!(
+            (b"", "31d6cfe0d16ae931b73c59d7e0c089c0"),
+            (b"a", "bde52cb31de33e46245e05fbdbd6fb24"),
+            (b"message digest", "d9130a8164549fe818874806e1c7014b"),
+            (b"abcdefghijklmnopqrstuvwxyz", "d79e1c308aa5bbcdeea8ed63df412da9"),
+            (b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "e33b4ddc9c38f2199c3e7b164fcc0536"),
+        );
+        for (input, expected) in vectors {
+            assert_eq!(decimals_to_hex_string(super::Md4::digest(input)), expected);
+        }
+    }
+
+    #[test]
+    fn padding_is_little_endian() {
+        let padding = super::Md4::padding(3);
+        assert_eq!(padding.len(), 61);
+        assert_eq!(padding[53], 24);
+        assert_eq!(padding[60], 0);
+    }
+}
@@ -0,0 +1,114 @@
+use hash::MerkleDamgard;
+
+// per-round shift amounts
+static SHIFTS: [u32; 16] = [7, 12, 17, 22, 5, 9, 14, 20, 4, 11, 16, 23, 6, 10, 15, 21];
+
+// the constants added in each step, from RFC 1321: the integer part of
+// abs(sin(i + 1)) * 2^32
+const K: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee,
+    0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be,
+    0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa,
+    0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed,
+    0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c,
+    0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05,
+    0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039,
+    0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1,
+    0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+/// MD5, as specified in RFC 1321.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::hash::MerkleDamgard;
+/// use cryptopalslib::hash::md5::Md5;
+///
+/// let digest = Md5::digest(b"abc");
+/// assert_eq!(cryptopalslib::convert::decimals_to_hex_string(digest), "900150983cd24fb0d6963f7d28e17f72");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Md5;
+
+impl MerkleDamgard for Md5 {
+    type State = [u32; 4];
+
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 16;
+    const BIG_ENDIAN_LENGTH: bool = false;
+
+    fn initial_state() -> [u32; 4] {
+        [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476]
+    }
+
+    fn compress(state: &mut [u32; 4], block: &[u8]) {
+        let mut m = [0u32; 16];
+        ::hash::read_le_words(block, &mut m);
+
+        let [mut a, mut b, mut c, mut d] = *state;
+        for index in 0..64 {
+            let (f, g) = match index / 16 {
+                0 => ((b & c) | (!b & d), index),
+                1 => ((d & b) | (!d & c), (5 * index + 1) % 16),
+                2 => (b ^ c ^ d, (3 * index + 5) % 16),
+                _ => (c ^ (b | !d), (7 * index) % 16),
+            };
+            let shift = SHIFTS[(index / 16) * 4 + index % 4];
+            let rotated = a.wrapping_add(f)
+                .wrapping_add(K[index])
+                .wrapping_add(m[g])
+                .rotate_left(shift);
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(rotated);
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+    }
+
+    fn state_to_digest(state: &[u32; 4]) -> Vec<u8> {
+        ::hash::write_le_words(state)
+    }
+
+    fn digest_to_state(digest: &[u8]) -> [u32; 4] {
+        if digest.len() != 16 {
+            panic!("MD5 digests are 16 bytes long.");
+        }
+        let mut state = [0; 4];
+        ::hash::read_le_words(digest, &mut state);
+        state
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use convert::decimals_to_hex_string;
+    use hash::MerkleDamgard;
+
+    #[test]
+    fn rfc_1321_vectors() {
+        let vectors: Vec<(&[u8], &str)> = vec!(
+            (b"", "d41d8cd98f00b204e9800998ecf8427e"),
+            (b"a", "0cc175b9c0f1b6a831c399e269772661"),
+            (b"message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            (b"abcdefghijklmnopqrstuvwxyz", "c3fcd3d76192e4007dfb496cca67e13b"),
+            (b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "57edf4a22be3c955ac49da2e2107b67a"),
+        );
+        for (input, expected) in vectors {
+            assert_eq!(decimals_to_hex_string(super::Md5::digest(input)), expected);
+        }
+    }
+}
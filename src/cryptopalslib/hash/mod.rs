@@ -0,0 +1,225 @@
+use std::marker::PhantomData;
+
+pub mod md4;
+pub mod md5;
+pub mod sha1;
+pub mod sha256;
+
+/// A hash function built with the Merkle–Damgård construction: the message
+/// is padded to a whole number of blocks, and a compression function folds
+/// each block into a fixed-size state. The final state is the digest.
+///
+/// Since the digest is the whole state, anyone who knows a digest can pick
+/// up where the hash left off, which is what `Hasher::from_digest` allows.
+pub trait MerkleDamgard {
+    /// The internal state, such as `[u32; 5]` for SHA-1.
+    type State: Copy;
+
+    /// The size of a block, in bytes.
+    const BLOCK_SIZE: usize;
+
+    /// The size of a digest, in bytes.
+    const OUTPUT_SIZE: usize;
+
+    /// Whether the message length at the end of the padding is big-endian.
+    const BIG_ENDIAN_LENGTH: bool;
+
+    /// The state before any blocks have been compressed.
+    fn initial_state() -> Self::State;
+
+    /// Folds one block into the state.
+    fn compress(state: &mut Self::State, block: &[u8]);
+
+    /// Serializes the state into a digest.
+    fn state_to_digest(state: &Self::State) -> Vec<u8>;
+
+    /// Parses a digest back into the state that produced it.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the digest isn't `OUTPUT_SIZE` bytes long.
+    fn digest_to_state(digest: &[u8]) -> Self::State;
+
+    /// Returns the padding appended to a message of `length` bytes: a 1 bit,
+    /// enough 0 bits to leave room for the length at the end of a block, and
+    /// then the message length in bits as a 64-bit number.
+    fn padding(length: u64) -> Vec<u8> {
+        let mut output = vec!(0x80);
+        while (length as usize + output.len()) % Self::BLOCK_SIZE != Self::BLOCK_SIZE - 8 {
+            output.push(0);
+        }
+        let bits = length.wrapping_mul(8);
+        for index in 0..8 {
+            let shift = if Self::BIG_ENDIAN_LENGTH { 56 - index * 8 } else { index * 8 };
+            output.push((bits >> shift) as u8);
+        }
+        output
+    }
+
+    /// Hashes a complete message.
+    fn digest(data: &[u8]) -> Vec<u8> where Self: Sized {
+        let mut hasher = Hasher::<Self>::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+/// Incrementally hashes a message with any `MerkleDamgard` hash function.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::hash::Hasher;
+/// use cryptopalslib::hash::sha1::Sha1;
+///
+/// let mut hasher = Hasher::<Sha1>::new();
+/// hasher.update(b"a");
+/// hasher.update(b"bc");
+/// let digest = hasher.finalize();
+/// assert_eq!(cryptopalslib::convert::decimals_to_hex_string(digest), "a9993e364706816aba3e25717850c26c9cd0d89d");
+/// ```
+pub struct Hasher<H: MerkleDamgard> {
+    state: H::State,
+    buffer: Vec<u8>,
+    // the number of bytes processed so far, including any in the buffer
+    length: u64,
+    hash: PhantomData<H>,
+}
+
+impl<H: MerkleDamgard> Hasher<H> {
+    /// Creates a hasher with the standard initial state.
+    pub fn new() -> Hasher<H> {
+        Hasher::from_state(H::initial_state(), 0)
+    }
+
+    /// Creates a hasher that picks up from the given state, as if `length`
+    /// bytes had already been hashed.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `length` isn't a multiple of the block size.
+    pub fn from_state(state: H::State, length: u64) -> Hasher<H> {
+        if length % H::BLOCK_SIZE as u64 != 0 {
+            panic!("Length should be a multiple of the block size.");
+        }
+        Hasher { state, buffer: vec!(), length, hash: PhantomData }
+    }
+
+    /// Creates a hasher that picks up from the state behind a digest, as if
+    /// `length` bytes (including padding) had already been hashed.
+    pub fn from_digest(digest: &[u8], length: u64) -> Hasher<H> {
+        Hasher::from_state(H::digest_to_state(digest), length)
+    }
+
+    /// Returns the current internal state. Only whole blocks are reflected
+    /// in it; buffered bytes aren't.
+    pub fn state(&self) -> H::State {
+        self.state
+    }
+
+    /// Adds data to the message being hashed.
+    pub fn update(&mut self, data: &[u8]) {
+        self.length += data.len() as u64;
+        self.buffer.extend_from_slice(data);
+
+        let blocks = self.buffer.len() / H::BLOCK_SIZE;
+        for index in 0..blocks {
+            H::compress(&mut self.state, &self.buffer[index * H::BLOCK_SIZE..(index + 1) * H::BLOCK_SIZE]);
+        }
+        self.buffer = self.buffer[blocks * H::BLOCK_SIZE..].to_vec();
+    }
+
+    /// Pads the message and returns its digest.
+    pub fn finalize(mut self) -> Vec<u8> {
+        let padding = H::padding(self.length);
+        self.update(&padding);
+        H::state_to_digest(&self.state)
+    }
+}
+
+impl<H: MerkleDamgard> Default for Hasher<H> {
+    fn default() -> Hasher<H> {
+        Hasher::new()
+    }
+}
+
+impl<H: MerkleDamgard> Clone for Hasher<H> {
+    fn clone(&self) -> Hasher<H> {
+        Hasher { state: self.state, buffer: self.buffer.clone(), length: self.length, hash: PhantomData }
+    }
+}
+
+/// Reads big-endian 32-bit words from a block.
+fn read_be_words(block: &[u8], words: &mut [u32]) {
+    for (word, chunk) in words.iter_mut().zip(block.chunks(4)) {
+        *word = chunk.iter().fold(0, |acc, &x| (acc << 8) | x as u32);
+    }
+}
+
+/// Reads little-endian 32-bit words from a block.
+fn read_le_words(block: &[u8], words: &mut [u32]) {
+    for (word, chunk) in words.iter_mut().zip(block.chunks(4)) {
+        *word = chunk.iter().rev().fold(0, |acc, &x| (acc << 8) | x as u32);
+    }
+}
+
+/// Writes 32-bit words out as big-endian bytes.
+fn write_be_words(words: &[u32]) -> Vec<u8> {
+    let mut output = vec!();
+    for word in words {
+        for index in 0..4 {
+            output.push((word >> (24 - index * 8)) as u8);
+        }
+    }
+    output
+}
+
+/// Writes 32-bit words out as little-endian bytes.
+fn write_le_words(words: &[u32]) -> Vec<u8> {
+    let mut output = vec!();
+    for word in words {
+        for index in 0..4 {
+            output.push((word >> (index * 8)) as u8);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Hasher, MerkleDamgard};
+    use super::md4::Md4;
+    use super::md5::Md5;
+    use super::sha1::Sha1;
+    use super::sha256::Sha256;
+
+    fn streaming_matches_digest<H: MerkleDamgard>() {
+        let message: Vec<u8> = (0..300).map(|x| x as u8).collect();
+        for chunk_size in [1, 7, 64, 100].iter() {
+            let mut hasher = Hasher::<H>::new();
+            for chunk in message.chunks(*chunk_size) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.finalize(), H::digest(&message));
+        }
+    }
+
+    fn digest_roundtrips_through_state<H: MerkleDamgard>() {
+        let digest = H::digest(b"round trip");
+        assert_eq!(digest.len(), H::OUTPUT_SIZE);
+        assert_eq!(H::state_to_digest(&H::digest_to_state(&digest)), digest);
+    }
+
+    #[test]
+    fn all_hashes() {
+        streaming_matches_digest::<Md4>();
+        streaming_matches_digest::<Md5>();
+        streaming_matches_digest::<Sha1>();
+        streaming_matches_digest::<Sha256>();
+
+        digest_roundtrips_through_state::<Md4>();
+        digest_roundtrips_through_state::<Md5>();
+        digest_roundtrips_through_state::<Sha1>();
+        digest_roundtrips_through_state::<Sha256>();
+    }
+}
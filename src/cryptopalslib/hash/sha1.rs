@@ -0,0 +1,126 @@
+use hash::MerkleDamgard;
+
+/// SHA-1, as specified in FIPS 180-4.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::hash::MerkleDamgard;
+/// use cryptopalslib::hash::sha1::Sha1;
+///
+/// let digest = Sha1::digest(b"");
+/// assert_eq!(cryptopalslib::convert::decimals_to_hex_string(digest), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Sha1;
+
+impl MerkleDamgard for Sha1 {
+    type State = [u32; 5];
+
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 20;
+    const BIG_ENDIAN_LENGTH: bool = true;
+
+    fn initial_state() -> [u32; 5] {
+        [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0]
+    }
+
+    fn compress(state: &mut [u32; 5], block: &[u8]) {
+        let mut w = [0u32; 80];
+        ::hash::read_be_words(block, &mut w[..16]);
+        for index in 16..80 {
+            w[index] = (w[index - 3] ^ w[index - 8] ^ w[index - 14] ^ w[index - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = *state;
+        for (index, &word) in w.iter().enumerate() {
+            let (f, k) = match index / 20 {
+                0 => ((b & c) | (!b & d), 0x5a827999),
+                1 => (b ^ c ^ d, 0x6ed9eba1),
+                2 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+                _ => (b ^ c ^ d, 0xca62c1d6),
+            };
+            let temp = a.rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+        state[4] = state[4].wrapping_add(e);
+    }
+
+    fn state_to_digest(state: &[u32; 5]) -> Vec<u8> {
+        ::hash::write_be_words(state)
+    }
+
+    fn digest_to_state(digest: &[u8]) -> [u32; 5] {
+        if digest.len() != 20 {
+            panic!("SHA-1 digests are 20 bytes long.");
+        }
+        let mut state = [0; 5];
+        ::hash::read_be_words(digest, &mut state);
+        state
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use convert::decimals_to_hex_string;
+    use hash::{Hasher, MerkleDamgard};
+
+    #[test]
+    fn fips_180_vectors() {
+        let output = super::Sha1::digest(b"abc");
+        assert_eq!(decimals_to_hex_string(output), "a9993e364706816aba3e25717850c26c9cd0d89d");
+
+        let output = super::Sha1::digest(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq");
+        assert_eq!(decimals_to_hex_string(output), "84983e441c3bd26ebaae4aa1f95129e5e54670f1");
+    }
+
+    #[test]
+    fn million_a() {
+        let mut hasher = Hasher::<super::Sha1>::new();
+        for _ in 0..1000 {
+            hasher.update(&[b'a'; 1000]);
+        }
+        assert_eq!(decimals_to_hex_string(hasher.finalize()), "34aa973cd4c4daa4f61eeb2bdbad27316534016f");
+    }
+
+    #[test]
+    fn resume_from_state() {
+        // hash a block, then pick up from the state it left behind
+        let message = [b'x'; 100];
+        let mut first = Hasher::<super::Sha1>::new();
+        first.update(&message[..64]);
+
+        let mut resumed = Hasher::<super::Sha1>::from_state(first.state(), 64);
+        resumed.update(&message[64..]);
+        assert_eq!(resumed.finalize(), super::Sha1::digest(&message));
+    }
+
+    #[test]
+    fn padding() {
+        let padding = super::Sha1::padding(3);
+        assert_eq!(padding.len(), 61);
+        assert_eq!(padding[0], 0x80);
+        assert_eq!(padding[60], 24);
+    }
+
+    #[test]
+    fn digest_to_state() {
+        let digest = super::Sha1::digest(b"abc");
+        let state = super::Sha1::digest_to_state(&digest);
+        assert_eq!(state[0], 0xa9993e36);
+        assert_eq!(super::Sha1::state_to_digest(&state), digest);
+    }
+}
@@ -0,0 +1,214 @@
+use hash::sha1::Sha1;
+use std::io;
+use std::io::prelude::*;
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// how long a connection can go without sending anything before it's
+// dropped, so a silent client can't hold up the server
+const READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A tiny HTTP server for the HMAC timing leak challenges. It only listens
+/// on the loopback interface.
+///
+/// Requests look like `GET /test?file=foo&signature=<hex>`. The server
+/// responds with 200 if the signature is the HMAC-SHA1 of the file name
+/// under its key, and 500 otherwise. Signatures are checked with
+/// `mac::insecure_compare_with`, sleeping for `delay` after each matching
+/// byte.
+///
+/// The server stops when it's dropped.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use cryptopalslib::hmac_server::{request, HmacServer};
+///
+/// let server = HmacServer::start(b"key".to_vec(), Duration::from_millis(0)).unwrap();
+/// let (status, _) = request(server.address(), "foo", &[0; 20]).unwrap();
+/// assert_eq!(status, 500);
+/// ```
+pub struct HmacServer {
+    address: SocketAddr,
+    running: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl HmacServer {
+    /// Starts the server on a free port on 127.0.0.1.
+    pub fn start(key: Vec<u8>, delay: Duration) -> io::Result<HmacServer> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let address = listener.local_addr()?;
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread_running = running.clone();
+        let handle = thread::spawn(move || {
+            for stream in listener.incoming() {
+                if !thread_running.load(Ordering::SeqCst) {
+                    break;
+                }
+                // requests are handled one at a time, so they don't
+                // interfere with each other's timing
+                if let Ok(stream) = stream {
+                    if let Err(e) = handle_connection(stream, &key, delay) {
+                        debug!("error handling request: {:?}", e);
+                    }
+                }
+            }
+        });
+
+        Ok(HmacServer { address, running, handle: Some(handle) })
+    }
+
+    /// The address the server is listening on.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+}
+
+impl Drop for HmacServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        // wake up the accept loop so it notices it should stop
+        let _ = TcpStream::connect(self.address);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, key: &[u8], delay: Duration) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let request_line = read_request_line(&mut stream)?;
+    let status = match parse_request(&request_line) {
+        Some((file, signature)) => {
+            let expected = ::mac::hmac::<Sha1>(key, file.as_bytes());
+            let valid = ::mac::insecure_compare_with(&expected, &signature, || {
+                if delay > Duration::from_millis(0) {
+                    thread::sleep(delay);
+                }
+            });
+            if valid { "200 OK" } else { "500 Internal Server Error" }
+        },
+        None => "400 Bad Request",
+    };
+
+    let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+    stream.write_all(response.as_bytes())?;
+    stream.shutdown(Shutdown::Both)
+}
+
+/// Reads the request up to the end of its headers, and returns the first
+/// line.
+fn read_request_line(stream: &mut TcpStream) -> io::Result<String> {
+    let mut data = vec!();
+    let mut buffer = [0; 1024];
+    while !data.windows(4).any(|x| x == b"\r\n\r\n") {
+        let count = stream.read(&mut buffer)?;
+        if count == 0 {
+            break;
+        }
+        data.extend_from_slice(&buffer[..count]);
+    }
+    let text = String::from_utf8_lossy(&data);
+    Ok(text.lines().next().unwrap_or("").to_string())
+}
+
+/// Pulls the file name and decoded signature out of a request line like
+/// `GET /test?file=foo&signature=abcd HTTP/1.1`.
+fn parse_request(line: &str) -> Option<(String, Vec<u8>)> {
+    let mut parts = line.split(' ');
+    if parts.next() != Some("GET") {
+        return None;
+    }
+    let target = parts.next()?;
+    let mut target_parts = target.splitn(2, '?');
+    if target_parts.next() != Some("/test") {
+        return None;
+    }
+
+    let mut file = None;
+    let mut signature = None;
+    for pair in target_parts.next()?.split('&') {
+        let mut pair_parts = pair.splitn(2, '=');
+        match (pair_parts.next(), pair_parts.next()) {
+            (Some("file"), Some(value)) => file = Some(value.to_string()),
+            (Some("signature"), Some(value)) => signature = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    let signature = signature?;
+    let is_hex = signature.len() % 2 == 0 && signature.chars().all(|x| x.is_ascii_hexdigit());
+    if !is_hex {
+        return None;
+    }
+    Some((file?, ::convert::hex_string_to_decimal_pairs(&signature)))
+}
+
+/// Sends a request to the server, returning the response status and how
+/// long the request took.
+pub fn request(address: SocketAddr, file: &str, signature: &[u8]) -> io::Result<(u16, Duration)> {
+    let request = format!("GET /test?file={}&signature={} HTTP/1.1\r\nHost: {}\r\n\r\n",
+        file, ::convert::decimals_to_hex_string(signature.to_vec()), address);
+
+    let start = Instant::now();
+    let mut stream = TcpStream::connect(address)?;
+    stream.write_all(request.as_bytes())?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let elapsed = start.elapsed();
+
+    let status = response.split(' ').nth(1).and_then(|x| x.parse().ok());
+    match status {
+        Some(status) => Ok((status, elapsed)),
+        None => Err(io::Error::new(io::ErrorKind::InvalidData, "malformed response")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hash::sha1::Sha1;
+    use std::net::TcpStream;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn valid_and_invalid_signatures() {
+        let server = super::HmacServer::start(b"secret".to_vec(), Duration::from_millis(0)).unwrap();
+        let signature = ::mac::hmac::<Sha1>(b"secret", b"foo");
+
+        let (status, _) = super::request(server.address(), "foo", &signature).unwrap();
+        assert_eq!(status, 200);
+        let (status, _) = super::request(server.address(), "bar", &signature).unwrap();
+        assert_eq!(status, 500);
+    }
+
+    #[test]
+    fn silent_client() {
+        let server = super::HmacServer::start(b"secret".to_vec(), Duration::from_millis(0)).unwrap();
+        let start = Instant::now();
+
+        // a client that never sends anything only holds up the others until
+        // it times out
+        let _silent = TcpStream::connect(server.address()).unwrap();
+        let (status, _) = super::request(server.address(), "foo", &[0; 20]).unwrap();
+        assert_eq!(status, 500);
+
+        // and stopping the server doesn't wait on it forever either
+        let _silent = TcpStream::connect(server.address()).unwrap();
+        drop(server);
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn parse_request() {
+        let parsed = super::parse_request("GET /test?file=foo&signature=0aff HTTP/1.1");
+        assert_eq!(parsed, Some(("foo".to_string(), vec!(10, 255))));
+        assert_eq!(super::parse_request("GET /test?file=foo&signature=xyz HTTP/1.1"), None);
+        assert_eq!(super::parse_request("GET /other?file=foo&signature=00 HTTP/1.1"), None);
+    }
+}
@@ -1,6 +1,25 @@
 #[macro_use]
 extern crate log;
+extern crate openssl;
 
+pub mod aes;
+pub mod attacks;
+pub mod bignum;
+pub mod clock;
 pub mod convert;
+pub mod cookie;
+pub mod der;
+pub mod dh;
+pub mod dsa;
+pub mod error;
+pub mod gf2;
+pub mod hash;
+pub mod hmac_server;
+pub mod mac;
+pub mod protocol;
+pub mod rng;
+pub mod rsa;
 pub mod score;
+pub mod srp;
+pub mod timing;
 pub mod xor;
@@ -0,0 +1,207 @@
+use hash::{Hasher, MerkleDamgard};
+
+/// Computes a secret-prefix MAC: the hash of the key followed by the
+/// message.
+///
+/// This is vulnerable to length extension with any `MerkleDamgard` hash,
+/// see `attacks::length_extension`.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::hash::sha1::Sha1;
+///
+/// let mac = cryptopalslib::mac::secret_prefix::<Sha1>(b"YELLOW SUBMARINE", b"hello");
+/// assert_eq!(mac.len(), 20);
+/// ```
+pub fn secret_prefix<H: MerkleDamgard>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut input = key.to_vec();
+    input.extend_from_slice(message);
+    H::digest(&input)
+}
+
+/// HMAC, as specified in RFC 2104, over any of the library's hashes.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::hash::sha256::Sha256;
+/// use cryptopalslib::mac::Hmac;
+///
+/// let mut hmac = Hmac::<Sha256>::new(b"key");
+/// hmac.update(b"The quick brown fox ");
+/// hmac.update(b"jumps over the lazy dog");
+/// assert_eq!(cryptopalslib::convert::decimals_to_hex_string(hmac.finalize()),
+///     "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8");
+/// ```
+pub struct Hmac<H: MerkleDamgard> {
+    inner: Hasher<H>,
+    outer_key: Vec<u8>,
+}
+
+impl<H: MerkleDamgard> Hmac<H> {
+    /// Creates an HMAC with the given key. Keys longer than the hash's block
+    /// size are hashed first.
+    pub fn new(key: &[u8]) -> Hmac<H> {
+        let mut block_key = if key.len() > H::BLOCK_SIZE {
+            H::digest(key)
+        } else {
+            key.to_vec()
+        };
+        block_key.resize(H::BLOCK_SIZE, 0);
+
+        let inner_key: Vec<u8> = block_key.iter().map(|x| x ^ 0x36).collect();
+        let outer_key: Vec<u8> = block_key.iter().map(|x| x ^ 0x5c).collect();
+
+        let mut inner = Hasher::<H>::new();
+        inner.update(&inner_key);
+        Hmac { inner, outer_key }
+    }
+
+    /// Adds data to the message being authenticated.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Returns the MAC of the message.
+    pub fn finalize(self) -> Vec<u8> {
+        let mut outer = Hasher::<H>::new();
+        outer.update(&self.outer_key);
+        outer.update(&self.inner.finalize());
+        outer.finalize()
+    }
+}
+
+/// Computes the HMAC of a complete message.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::hash::sha1::Sha1;
+///
+/// let mac = cryptopalslib::mac::hmac::<Sha1>(b"key", b"The quick brown fox jumps over the lazy dog");
+/// assert_eq!(cryptopalslib::convert::decimals_to_hex_string(mac), "de7c9b85b8b78aa6bc8a7a36f70a90701c9db4d9");
+/// ```
+pub fn hmac<H: MerkleDamgard>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut hmac = Hmac::<H>::new(key);
+    hmac.update(message);
+    hmac.finalize()
+}
+
+/// Computes a CBC-MAC with AES-128: the last block of the CBC encryption
+/// of the PKCS#7 padded message.
+///
+/// It's only safe for messages of one fixed length, with a fixed IV. If
+/// the IV comes with the message, whoever sends it can change the first
+/// block, and with variable length messages, MACs can be chained into new
+/// ones. See `attacks::cbc_mac`.
+///
+/// # Examples
+///
+/// ```
+/// let mac = cryptopalslib::mac::cbc_mac(b"YELLOW SUBMARINE", &[0; 16], b"alert('MZA who was that?');\n");
+/// assert_eq!(cryptopalslib::convert::decimals_to_hex_string(mac), "296b8d7cb78a243dda4d0a61d33bbdd1");
+/// ```
+pub fn cbc_mac(key: &[u8], iv: &[u8], message: &[u8]) -> Vec<u8> {
+    let ciphertext = ::aes::cbc_encrypt(key, iv, message);
+    ciphertext[ciphertext.len() - 16..].to_vec()
+}
+
+/// Compares two byte strings in time that depends only on their lengths,
+/// not on where they differ. Use this to check MACs.
+///
+/// # Examples
+///
+/// ```
+/// assert!(cryptopalslib::mac::constant_time_compare(b"abc", b"abc"));
+/// assert!(!cryptopalslib::mac::constant_time_compare(b"abc", b"abd"));
+/// ```
+pub fn constant_time_compare(first: &[u8], second: &[u8]) -> bool {
+    if first.len() != second.len() {
+        return false;
+    }
+    let difference = first.iter().zip(second.iter()).fold(0, |acc, (x, y)| acc | (x ^ y));
+    difference == 0
+}
+
+/// Compares two byte strings one byte at a time, returning as soon as a
+/// byte differs. This leaks how many leading bytes match through timing,
+/// and is only here to be attacked.
+///
+/// # Examples
+///
+/// ```
+/// assert!(cryptopalslib::mac::insecure_compare(b"abc", b"abc"));
+/// assert!(!cryptopalslib::mac::insecure_compare(b"abc", b"xbc"));
+/// ```
+pub fn insecure_compare(first: &[u8], second: &[u8]) -> bool {
+    insecure_compare_with(first, second, || ())
+}
+
+/// Like `insecure_compare`, but calls `on_match` after each matching byte.
+/// Sleeping in `on_match` exaggerates the timing leak.
+///
+/// # Examples
+///
+/// ```
+/// let mut matches = 0;
+/// cryptopalslib::mac::insecure_compare_with(b"abc", b"abd", || matches += 1);
+/// assert_eq!(matches, 2);
+/// ```
+pub fn insecure_compare_with<F: FnMut()>(first: &[u8], second: &[u8], mut on_match: F) -> bool {
+    if first.len() != second.len() {
+        return false;
+    }
+    for (x, y) in first.iter().zip(second.iter()) {
+        if x != y {
+            return false;
+        }
+        on_match();
+    }
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use convert::decimals_to_hex_string;
+    use hash::md5::Md5;
+    use hash::sha1::Sha1;
+    use hash::sha256::Sha256;
+
+    #[test]
+    fn rfc_2104_md5() {
+        let output = super::hmac::<Md5>(&[0x0b; 16], b"Hi There");
+        assert_eq!(decimals_to_hex_string(output), "9294727a3638bb1c13f48ef8158bfc9d");
+
+        let output = super::hmac::<Md5>(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(decimals_to_hex_string(output), "750c783e6ab0b503eaa86e310a5db738");
+    }
+
+    #[test]
+    fn rfc_2202_sha1() {
+        let output = super::hmac::<Sha1>(&[0x0b; 20], b"Hi There");
+        assert_eq!(decimals_to_hex_string(output), "b617318655057264e28bc0b6fb378c8ef146be00");
+
+        let output = super::hmac::<Sha1>(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(decimals_to_hex_string(output), "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79");
+    }
+
+    #[test]
+    fn rfc_4231_sha256() {
+        let output = super::hmac::<Sha256>(&[0x0b; 20], b"Hi There");
+        assert_eq!(decimals_to_hex_string(output), "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7");
+
+        let output = super::hmac::<Sha256>(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(decimals_to_hex_string(output), "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+
+        // key longer than the block size
+        let output = super::hmac::<Sha256>(&[0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First");
+        assert_eq!(decimals_to_hex_string(output), "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54");
+    }
+
+    #[test]
+    fn compare_different_lengths() {
+        assert!(!super::constant_time_compare(b"abc", b"abcd"));
+        assert!(!super::insecure_compare(b"abc", b"abcd"));
+    }
+}
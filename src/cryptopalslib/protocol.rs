@@ -0,0 +1,198 @@
+use dh::{Endpoint, Group, Message};
+use error::Error;
+use std::thread;
+use std::time::Duration;
+
+/// How Alice and Bob agree on a group and swap public keys.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Handshake {
+    /// Alice sends the group and her public key together, and Bob replies
+    /// with his public key (challenge 34).
+    Direct,
+    /// Alice proposes a group, Bob acknowledges it, and then they swap
+    /// public keys (challenge 35).
+    Negotiated,
+}
+
+/// Which way a message is travelling.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    ToBob,
+    ToAlice,
+}
+
+/// Encrypts a message under a session key with AES-CBC and a random IV.
+pub fn encrypt_message(key: &[u8], plaintext: &[u8]) -> Message {
+    let iv = ::openssl::crypto::rand::rand_bytes(16);
+    let ciphertext = ::aes::cbc_encrypt(key, &iv, plaintext);
+    Message::Data { ciphertext, iv }
+}
+
+/// Decrypts a message made by `encrypt_message`.
+pub fn decrypt_message(key: &[u8], message: &Message) -> Result<Vec<u8>, Error> {
+    match *message {
+        Message::Data { ref ciphertext, ref iv } => ::aes::cbc_decrypt(key, iv, ciphertext),
+        _ => Err(Error::UnexpectedMessage),
+    }
+}
+
+/// Runs Alice's side: agree on a session key in `group`, send `plaintext`,
+/// and return what Bob echoes back.
+pub fn alice(endpoint: &Endpoint<Message>, group: &Group, handshake: Handshake, plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+    let key = match handshake {
+        Handshake::Direct => ::dh::initiate(endpoint, group)?,
+        Handshake::Negotiated => {
+            endpoint.send(Message::Negotiate(group.clone()))?;
+            if endpoint.recv()? != Message::Ack {
+                return Err(Error::UnexpectedMessage);
+            }
+            let keys = group.generate_keypair();
+            endpoint.send(Message::PublicKey(keys.public.clone()))?;
+            match endpoint.recv()? {
+                Message::PublicKey(public) => ::dh::derive_key(&group.shared_secret(&keys, &public)),
+                _ => return Err(Error::UnexpectedMessage),
+            }
+        },
+    };
+
+    endpoint.send(encrypt_message(&key, plaintext))?;
+    decrypt_message(&key, &endpoint.recv()?)
+}
+
+/// Runs Bob's side: agree on a session key with whatever group Alice asks
+/// for, then decrypt her message and echo it back under a fresh IV.
+///
+/// Returns the message Bob received.
+pub fn bob(endpoint: &Endpoint<Message>, handshake: Handshake) -> Result<Vec<u8>, Error> {
+    let key = match handshake {
+        Handshake::Direct => ::dh::respond(endpoint)?,
+        Handshake::Negotiated => {
+            let group = match endpoint.recv()? {
+                Message::Negotiate(group) => group,
+                _ => return Err(Error::UnexpectedMessage),
+            };
+            endpoint.send(Message::Ack)?;
+            let public = match endpoint.recv()? {
+                Message::PublicKey(public) => public,
+                _ => return Err(Error::UnexpectedMessage),
+            };
+            let keys = group.generate_keypair();
+            endpoint.send(Message::PublicKey(keys.public.clone()))?;
+            ::dh::derive_key(&group.shared_secret(&keys, &public))
+        },
+    };
+
+    let plaintext = decrypt_message(&key, &endpoint.recv()?)?;
+    endpoint.send(encrypt_message(&key, &plaintext))?;
+    Ok(plaintext)
+}
+
+/// Something sitting between Alice and Bob.
+///
+/// Each message passing through is handed to `intercept`, and whatever it
+/// returns is delivered in its place: the same message to forward it, a
+/// changed one to rewrite it, nothing to drop it, or several to inject more.
+///
+/// Closures taking a `Direction` and a `Message` are interceptors too.
+pub trait Interceptor {
+    fn intercept(&mut self, direction: Direction, message: Message) -> Vec<Message>;
+}
+
+impl<F: FnMut(Direction, Message) -> Vec<Message>> Interceptor for F {
+    fn intercept(&mut self, direction: Direction, message: Message) -> Vec<Message> {
+        self(direction, message)
+    }
+}
+
+/// Passes messages between the endpoint connected to Alice and the one
+/// connected to Bob, through an interceptor, until either side hangs up.
+pub fn relay<I: Interceptor>(alice: &Endpoint<Message>, bob: &Endpoint<Message>, interceptor: &mut I) {
+    let poll = Duration::from_millis(1);
+    loop {
+        for &(direction, from, to) in [(Direction::ToBob, alice, bob), (Direction::ToAlice, bob, alice)].iter() {
+            let message = match from.recv_timeout(poll) {
+                Ok(Some(message)) => message,
+                Ok(None) => continue,
+                Err(_) => return,
+            };
+            for output in interceptor.intercept(direction, message) {
+                if to.send(output).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Runs Alice and Bob on their own threads, with Mallory's interceptor
+/// relaying between them on this one.
+///
+/// Returns what Alice got back from her echo, and what Bob received.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::bignum::BigUint;
+/// use cryptopalslib::dh::{Group, Message};
+/// use cryptopalslib::protocol::{run, Direction, Handshake};
+///
+/// let group = Group::new(BigUint::from(0xffffffef), BigUint::from(5));
+/// let mut seen = 0;
+/// let (alice, bob) = run(&group, Handshake::Direct, b"hi bob", &mut |_: Direction, message: Message| {
+///     seen += 1;
+///     vec!(message)
+/// });
+/// assert_eq!(alice.unwrap(), b"hi bob");
+/// assert_eq!(bob.unwrap(), b"hi bob");
+/// assert_eq!(seen, 4);
+/// ```
+pub fn run<I: Interceptor>(group: &Group, handshake: Handshake, plaintext: &[u8], interceptor: &mut I)
+    -> (Result<Vec<u8>, Error>, Result<Vec<u8>, Error>) {
+    let (alice_endpoint, mallory_alice) = ::dh::channel();
+    let (bob_endpoint, mallory_bob) = ::dh::channel();
+
+    let group = group.clone();
+    let plaintext = plaintext.to_vec();
+    let alice = thread::spawn(move || alice(&alice_endpoint, &group, handshake, &plaintext));
+    let bob = thread::spawn(move || bob(&bob_endpoint, handshake));
+
+    relay(&mallory_alice, &mallory_bob, interceptor);
+    // hang up, so neither side waits forever on a dropped message
+    drop(mallory_alice);
+    drop(mallory_bob);
+
+    (alice.join().unwrap(), bob.join().unwrap())
+}
+
+#[cfg(test)]
+mod test {
+    use dh::{Group, Message};
+    use error::Error;
+    use super::{Direction, Handshake};
+
+    fn forward(_: Direction, message: Message) -> Vec<Message> {
+        vec!(message)
+    }
+
+    #[test]
+    fn honest_exchange() {
+        for &handshake in [Handshake::Direct, Handshake::Negotiated].iter() {
+            let (alice, bob) = super::run(&Group::modp_1536(), handshake, b"YELLOW SUBMARINE", &mut forward);
+            assert_eq!(alice.unwrap(), b"YELLOW SUBMARINE");
+            assert_eq!(bob.unwrap(), b"YELLOW SUBMARINE");
+        }
+    }
+
+    #[test]
+    fn dropped_message() {
+        let group = Group::new(::bignum::BigUint::from(0xffffffef), ::bignum::BigUint::from(5));
+        let (alice, bob) = super::run(&group, Handshake::Direct, b"hello", &mut |direction, message| {
+            match (direction, message) {
+                (Direction::ToAlice, Message::Data { .. }) => vec!(),
+                (_, message) => vec!(message),
+            }
+        });
+        assert_eq!(alice, Err(Error::ChannelClosed));
+        assert_eq!(bob.unwrap(), b"hello");
+    }
+}
@@ -0,0 +1,43 @@
+pub mod mt19937;
+pub mod seed;
+
+/// A pseudorandom number generator.
+///
+/// Every generator implements `next_u32`. Generators with a 64-bit native
+/// output also override `next_u64`.
+pub trait Rng {
+    /// Returns the next 32 bits of output.
+    fn next_u32(&mut self) -> u32;
+
+    /// Returns the next 64 bits of output.
+    ///
+    /// By default, this joins two calls to `next_u32`, with the first call
+    /// forming the upper half.
+    fn next_u64(&mut self) -> u64 {
+        let high = self.next_u32() as u64;
+        let low = self.next_u32() as u64;
+        (high << 32) | low
+    }
+
+    /// Fills a buffer with output from the generator, one `next_u32` call
+    /// per 4 bytes, in big-endian order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cryptopalslib::rng::Rng;
+    ///
+    /// let mut rng = cryptopalslib::rng::mt19937::Mt19937::new(5489);
+    /// let mut buffer = [0; 6];
+    /// rng.fill_bytes(&mut buffer);
+    /// assert_eq!(buffer[..4], [0xd0, 0x91, 0xbb, 0x5c]);
+    /// ```
+    fn fill_bytes(&mut self, buffer: &mut [u8]) {
+        for chunk in buffer.chunks_mut(4) {
+            let output = self.next_u32();
+            for (index, byte) in chunk.iter_mut().enumerate() {
+                *byte = (output >> (24 - index * 8)) as u8;
+            }
+        }
+    }
+}
@@ -0,0 +1,454 @@
+use gf2::{Equation, System};
+use rng::Rng;
+
+const N: usize = 624;
+const M: usize = 397;
+const MATRIX_A: u32 = 0x9908b0df;
+const UPPER_MASK: u32 = 0x80000000;
+const LOWER_MASK: u32 = 0x7fffffff;
+
+const N_64: usize = 312;
+const M_64: usize = 156;
+const MATRIX_A_64: u64 = 0xb5026f5aa96619e9;
+const UPPER_MASK_64: u64 = 0xffffffff80000000;
+const LOWER_MASK_64: u64 = 0x7fffffff;
+
+/// The 32-bit Mersenne Twister, as described in Matsumoto and Nishimura's
+/// reference implementation (`mt19937ar.c`).
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::rng::Rng;
+///
+/// let mut rng = cryptopalslib::rng::mt19937::Mt19937::new(5489);
+/// assert_eq!(rng.next_u32(), 3499211612);
+/// ```
+#[derive(Clone)]
+pub struct Mt19937 {
+    state: [u32; N],
+    index: usize,
+}
+
+impl Mt19937 {
+    /// Creates a generator seeded with `init_genrand`.
+    pub fn new(seed: u32) -> Mt19937 {
+        let mut state = [0; N];
+        state[0] = seed;
+        for index in 1..N {
+            let previous = state[index - 1];
+            state[index] = 1812433253u32
+                .wrapping_mul(previous ^ (previous >> 30))
+                .wrapping_add(index as u32);
+        }
+        Mt19937 { state, index: N }
+    }
+
+    /// Regenerates the state array once all of its words have been used.
+    fn twist(&mut self) {
+        for index in 0..N {
+            let x = (self.state[index] & UPPER_MASK)
+                | (self.state[(index + 1) % N] & LOWER_MASK);
+            let mut x_a = x >> 1;
+            if x & 1 != 0 {
+                x_a ^= MATRIX_A;
+            }
+            self.state[index] = self.state[(index + M) % N] ^ x_a;
+        }
+        self.index = 0;
+    }
+}
+
+/// Applies MT19937's output tempering to a word of state.
+pub fn temper(word: u32) -> u32 {
+    let mut y = word;
+    y ^= y >> 11;
+    y ^= (y << 7) & 0x9d2c5680;
+    y ^= (y << 15) & 0xefc60000;
+    y ^= y >> 18;
+    y
+}
+
+/// Reverses MT19937's output tempering, recovering the word of state that
+/// produced an output.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::rng::mt19937::{temper, untemper};
+///
+/// assert_eq!(untemper(temper(0xdeadbeef)), 0xdeadbeef);
+/// ```
+pub fn untemper(output: u32) -> u32 {
+    let mut y = output;
+    y = undo_right_shift_xor(y, 18);
+    y = undo_left_shift_and_xor(y, 15, 0xefc60000);
+    y = undo_left_shift_and_xor(y, 7, 0x9d2c5680);
+    y = undo_right_shift_xor(y, 11);
+    y
+}
+
+/// Inverts `y ^= y >> shift`.
+fn undo_right_shift_xor(value: u32, shift: u32) -> u32 {
+    // each pass recovers another `shift` bits, working down from the top
+    let mut result = value;
+    for _ in 0..(32 / shift) {
+        result = value ^ (result >> shift);
+    }
+    result
+}
+
+/// Inverts `y ^= (y << shift) & mask`.
+fn undo_left_shift_and_xor(value: u32, shift: u32, mask: u32) -> u32 {
+    // each pass recovers another `shift` bits, working up from the bottom
+    let mut result = value;
+    for _ in 0..(32 / shift) {
+        result = value ^ ((result << shift) & mask);
+    }
+    result
+}
+
+/// Clones a generator from 624 consecutive outputs.
+///
+/// The returned generator continues from where the outputs left off, so its
+/// first output will match the 625th output of the original.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::rng::Rng;
+/// use cryptopalslib::rng::mt19937::{clone_from_outputs, Mt19937};
+///
+/// let mut original = Mt19937::new(1234);
+/// let mut outputs = [0; 624];
+/// for output in outputs.iter_mut() {
+///     *output = original.next_u32();
+/// }
+///
+/// let mut clone = clone_from_outputs(&outputs);
+/// assert_eq!(clone.next_u32(), original.next_u32());
+/// ```
+pub fn clone_from_outputs(outputs: &[u32; N]) -> Mt19937 {
+    let mut state = [0; N];
+    for (word, &output) in state.iter_mut().zip(outputs.iter()) {
+        *word = untemper(output);
+    }
+    Mt19937 { state, index: N }
+}
+
+/// Some or all of the bits of one output of a generator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observation {
+    /// The position of the output, counting from the first observation.
+    pub index: usize,
+    /// The output, with unknown bits set to 0.
+    pub value: u32,
+    /// Which bits of `value` are known.
+    pub mask: u32,
+}
+
+impl Observation {
+    /// An observation where every bit of the output is known.
+    pub fn full(index: usize, value: u32) -> Observation {
+        Observation { index, value, mask: 0xffffffff }
+    }
+}
+
+/// Clones a generator from observations that may have gaps, or only
+/// reveal some bits of each output.
+///
+/// Every output bit is a linear function (over GF(2)) of the 19968 bits of
+/// state that produced the first observed output, so each known bit gives
+/// one equation. Once enough independent equations are collected the state
+/// can be solved for, which takes roughly 20000 known bits spread across
+/// the outputs.
+///
+/// Returns a generator that continues after the last observed output, or
+/// `None` if the observations are inconsistent or don't pin down enough of
+/// the state to predict what comes next.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::rng::Rng;
+/// use cryptopalslib::rng::mt19937::{clone_from_partial_outputs, Mt19937, Observation};
+///
+/// // skip every fifth output, for a while
+/// let mut original = Mt19937::new(1234);
+/// let observations: Vec<Observation> = (0..1000)
+///     .map(|x| (x, original.next_u32()))
+///     .filter(|&(x, _)| x > 300 || x % 5 != 4)
+///     .map(|(x, y)| Observation::full(x, y))
+///     .collect();
+///
+/// let mut clone = clone_from_partial_outputs(&observations).unwrap();
+/// assert_eq!(clone.next_u32(), original.next_u32());
+/// ```
+pub fn clone_from_partial_outputs(observations: &[Observation]) -> Option<Mt19937> {
+    let length = match observations.iter().map(|x| x.index + 1).max() {
+        Some(x) => x,
+        None => return None,
+    };
+    let generations = (length - 1) / N + 1;
+
+    // symbolic[g][word][bit] lists the state variables that make up each bit
+    // of the state array after g twists
+    let mut symbolic = vec!(initial_symbolic_state());
+    for _ in 1..generations {
+        let next = symbolic_twist(symbolic.last().unwrap());
+        symbolic.push(next);
+    }
+
+    let tempering = tempering_matrix();
+    let mut system = System::new(N * 32);
+    for observation in observations {
+        let word = &symbolic[observation.index / N][observation.index % N];
+        for bit in 0..32 {
+            if observation.mask & (1 << bit) == 0 {
+                continue;
+            }
+            let mut variables = vec!();
+            for source in 0..32 {
+                if tempering[bit] & (1 << source) != 0 {
+                    variables.extend_from_slice(&word[source]);
+                }
+            }
+            let value = observation.value & (1 << bit) != 0;
+            if system.add(Equation::new(variables, value)).is_err() {
+                return None;
+            }
+        }
+    }
+
+    let solution = system.solve();
+    let mut state = [0; N];
+    for (word, value) in state.iter_mut().enumerate() {
+        for bit in 0..32 {
+            if solution.values[word * 32 + bit] {
+                *value |= 1 << bit;
+            }
+        }
+    }
+
+    // the lower 31 bits of the first word are never used by the next twist,
+    // so everything else in the final state array needs to be known
+    let last = &symbolic[generations - 1];
+    for word in 0..N {
+        for bit in 0..32 {
+            if word == 0 && bit < 31 {
+                continue;
+            }
+            if !last[word][bit].iter().all(|&x| solution.determined[x]) {
+                return None;
+            }
+        }
+    }
+
+    let mut clone = Mt19937 { state, index: N };
+    for _ in 1..generations {
+        clone.twist();
+    }
+    clone.index = length - (generations - 1) * N;
+    Some(clone)
+}
+
+/// The state array as variables: bit b of word w is variable `w * 32 + b`.
+fn initial_symbolic_state() -> Vec<Vec<Vec<usize>>> {
+    (0..N).map(|word| (0..32).map(|bit| vec!(word * 32 + bit)).collect()).collect()
+}
+
+/// Runs `Mt19937::twist` on a symbolic state array.
+fn symbolic_twist(previous: &[Vec<Vec<usize>>]) -> Vec<Vec<Vec<usize>>> {
+    let mut state = previous.to_vec();
+    for index in 0..N {
+        // x takes its top bit from this word and the rest from the next one
+        let next = (index + 1) % N;
+        let x: Vec<Vec<usize>> = (0..32)
+            .map(|bit| if bit == 31 { state[index][bit].clone() } else { state[next][bit].clone() })
+            .collect();
+
+        let mut word = vec!();
+        for bit in 0..32 {
+            let mut variables = state[(index + M) % N][bit].clone();
+            if bit < 31 {
+                variables = ::gf2::xor_sorted(&variables, &x[bit + 1]);
+            }
+            if MATRIX_A & (1 << bit) != 0 {
+                variables = ::gf2::xor_sorted(&variables, &x[0]);
+            }
+            word.push(variables);
+        }
+        state[index] = word;
+    }
+    state
+}
+
+/// Returns, for each output bit, a mask of the state bits that are XORed
+/// together by `temper` to produce it.
+fn tempering_matrix() -> [u32; 32] {
+    let mut matrix = [0; 32];
+    for source in 0..32 {
+        let column = temper(1 << source);
+        for bit in 0..32 {
+            if column & (1 << bit) != 0 {
+                matrix[bit] |= 1 << source;
+            }
+        }
+    }
+    matrix
+}
+
+impl Rng for Mt19937 {
+    fn next_u32(&mut self) -> u32 {
+        if self.index >= N {
+            self.twist();
+        }
+        let output = temper(self.state[self.index]);
+        self.index += 1;
+        output
+    }
+}
+
+impl Iterator for Mt19937 {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        Some(self.next_u32())
+    }
+}
+
+/// The 64-bit Mersenne Twister, as described in Matsumoto and Nishimura's
+/// reference implementation (`mt19937-64.c`).
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::rng::Rng;
+///
+/// let mut rng = cryptopalslib::rng::mt19937::Mt19937_64::new(5489);
+/// assert_eq!(rng.next_u64(), 14514284786278117030);
+/// ```
+#[derive(Clone)]
+pub struct Mt19937_64 {
+    state: [u64; N_64],
+    index: usize,
+}
+
+impl Mt19937_64 {
+    /// Creates a generator seeded with `init_genrand64`.
+    pub fn new(seed: u64) -> Mt19937_64 {
+        let mut state = [0; N_64];
+        state[0] = seed;
+        for index in 1..N_64 {
+            let previous = state[index - 1];
+            state[index] = 6364136223846793005u64
+                .wrapping_mul(previous ^ (previous >> 62))
+                .wrapping_add(index as u64);
+        }
+        Mt19937_64 { state, index: N_64 }
+    }
+
+    fn twist(&mut self) {
+        for index in 0..N_64 {
+            let x = (self.state[index] & UPPER_MASK_64)
+                | (self.state[(index + 1) % N_64] & LOWER_MASK_64);
+            let mut x_a = x >> 1;
+            if x & 1 != 0 {
+                x_a ^= MATRIX_A_64;
+            }
+            self.state[index] = self.state[(index + M_64) % N_64] ^ x_a;
+        }
+        self.index = 0;
+    }
+}
+
+impl Rng for Mt19937_64 {
+    /// The upper half of `next_u64`.
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        if self.index >= N_64 {
+            self.twist();
+        }
+        let mut y = self.state[self.index];
+        y ^= (y >> 29) & 0x5555555555555555;
+        y ^= (y << 17) & 0x71d67fffeda60000;
+        y ^= (y << 37) & 0xfff7eee000000000;
+        y ^= y >> 43;
+        self.index += 1;
+        y
+    }
+}
+
+impl Iterator for Mt19937_64 {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        Some(self.next_u64())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rng::Rng;
+
+    #[test]
+    fn mt19937_reference_outputs() {
+        let mut rng = super::Mt19937::new(5489);
+        let outputs: Vec<u32> = rng.by_ref().take(3).collect();
+        assert_eq!(outputs, vec!(3499211612, 581869302, 3890346734));
+        assert_eq!(rng.nth(10000 - 4), Some(4123659995));
+    }
+
+    #[test]
+    fn mt19937_64_reference_outputs() {
+        let mut rng = super::Mt19937_64::new(5489);
+        assert_eq!(rng.nth(10000 - 1), Some(9981545732273789042));
+    }
+
+    #[test]
+    fn mt19937_seed_1() {
+        let mut rng = super::Mt19937::new(1);
+        assert_eq!(rng.next_u32(), 1791095845);
+        assert_eq!(rng.next_u32(), 4282876139);
+    }
+
+    #[test]
+    fn untemper() {
+        let mut rng = super::Mt19937::new(5489);
+        rng.next_u32();
+        assert_eq!(super::untemper(3499211612), rng.state[0]);
+    }
+
+    #[test]
+    fn clone_from_truncated_outputs() {
+        // only the top 16 bits of each output are visible
+        let mut original = super::Mt19937::new(98765);
+        let observations: Vec<super::Observation> = (0..1300)
+            .map(|x| super::Observation { index: x, value: original.next_u32() & 0xffff0000, mask: 0xffff0000 })
+            .collect();
+
+        let mut clone = super::clone_from_partial_outputs(&observations).unwrap();
+        let expected: Vec<u32> = original.take(1000).collect();
+        let actual: Vec<u32> = clone.by_ref().take(1000).collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn clone_from_too_few_outputs() {
+        let mut original = super::Mt19937::new(98765);
+        let observations: Vec<super::Observation> = (0..600)
+            .map(|x| super::Observation::full(x, original.next_u32()))
+            .collect();
+        assert!(super::clone_from_partial_outputs(&observations).is_none());
+    }
+
+    #[test]
+    fn mt19937_64_next_u32_is_upper_half() {
+        let mut first = super::Mt19937_64::new(42);
+        let mut second = super::Mt19937_64::new(42);
+        assert_eq!(first.next_u32() as u64, second.next_u64() >> 32);
+    }
+}
@@ -0,0 +1,135 @@
+use clock::Clock;
+use rng::Rng;
+use rng::mt19937::Mt19937;
+
+/// Creates an MT19937 generator seeded with the current time.
+pub fn seed_from_time<C: Clock>(clock: &C) -> Mt19937 {
+    Mt19937::new(clock.now() as u32)
+}
+
+/// Finds the seed that makes MT19937's first output equal `output`, trying
+/// every seed from `start` to `end` inclusive.
+///
+/// When the seed is a timestamp, `start` and `end` are the earliest and
+/// latest times the generator could have been seeded.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::rng::Rng;
+/// use cryptopalslib::rng::mt19937::Mt19937;
+///
+/// let output = Mt19937::new(1500000123).next_u32();
+/// let seed = cryptopalslib::rng::seed::recover_seed(output, 1500000000, 1500001000);
+/// assert_eq!(seed, Some(1500000123));
+/// ```
+pub fn recover_seed(output: u32, start: u32, end: u32) -> Option<u32> {
+    (start..=end).find(|&x| Mt19937::new(x).next_u32() == output)
+}
+
+/// The number of bytes in a password reset token.
+pub const TOKEN_LENGTH: usize = 16;
+
+/// Generates a password reset token from an MT19937 generator seeded with
+/// the current time.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::clock::SimulatedClock;
+///
+/// let token = cryptopalslib::rng::seed::password_reset_token(&SimulatedClock::new(1500000000));
+/// assert_eq!(token.len(), 16);
+/// ```
+pub fn password_reset_token<C: Clock>(clock: &C) -> Vec<u8> {
+    let mut token = vec!(0; TOKEN_LENGTH);
+    seed_from_time(clock).fill_bytes(&mut token);
+    token
+}
+
+/// Checks whether a token was generated by `password_reset_token` at some
+/// point in the `window` seconds leading up to `now`.
+///
+/// Returns the timestamp the token was seeded with, if one is found.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::clock::SimulatedClock;
+/// use cryptopalslib::rng::seed::{find_token_seed, password_reset_token};
+///
+/// let token = password_reset_token(&SimulatedClock::new(1500000000));
+/// assert_eq!(find_token_seed(&token, 1500000300, 3600), Some(1500000000));
+/// assert_eq!(find_token_seed(&[0; 16], 1500000300, 3600), None);
+/// ```
+pub fn find_token_seed(token: &[u8], now: u64, window: u64) -> Option<u64> {
+    let mut candidate = vec!(0; token.len());
+    for seed in (now.saturating_sub(window)..=now).rev() {
+        Mt19937::new(seed as u32).fill_bytes(&mut candidate);
+        if candidate == token {
+            return Some(seed);
+        }
+    }
+    None
+}
+
+/// Encrypts or decrypts data with a stream cipher whose keystream comes
+/// from MT19937 seeded with a 16-bit key.
+///
+/// # Examples
+///
+/// ```
+/// let ciphertext = cryptopalslib::rng::seed::mt19937_cipher(1234, b"attack at dawn");
+/// let plaintext = cryptopalslib::rng::seed::mt19937_cipher(1234, &ciphertext);
+/// assert_eq!(plaintext, b"attack at dawn");
+/// ```
+pub fn mt19937_cipher(key: u16, data: &[u8]) -> Vec<u8> {
+    let mut keystream = vec!(0; data.len());
+    Mt19937::new(key as u32).fill_bytes(&mut keystream);
+    data.iter().zip(keystream.iter()).map(|(x, y)| x ^ y).collect()
+}
+
+/// Recovers the key for `mt19937_cipher` by trying all 65536 keys, given
+/// that the plaintext is known to end with `known_suffix`.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::rng::seed::{mt19937_cipher, recover_cipher_key};
+///
+/// let ciphertext = mt19937_cipher(4321, b"random prefixAAAAAAAAAAAAAA");
+/// assert_eq!(recover_cipher_key(&ciphertext, b"AAAAAAAAAAAAAA"), Some(4321));
+/// ```
+pub fn recover_cipher_key(ciphertext: &[u8], known_suffix: &[u8]) -> Option<u16> {
+    if known_suffix.len() > ciphertext.len() {
+        return None;
+    }
+    let offset = ciphertext.len() - known_suffix.len();
+    (0..65536u32)
+        .map(|x| x as u16)
+        .find(|&key| &mt19937_cipher(key, ciphertext)[offset..] == known_suffix)
+}
+
+#[cfg(test)]
+mod test {
+    use clock::{Clock, SimulatedClock};
+    use rng::Rng;
+
+    #[test]
+    fn recover_seed_at_end_of_range() {
+        let clock = SimulatedClock::new(1500000000);
+        clock.sleep(500);
+        let output = super::seed_from_time(&clock).next_u32();
+        assert_eq!(super::recover_seed(output, 1500000000, 1500000500), Some(1500000500));
+        assert_eq!(super::recover_seed(output, 1500000000, 1500000499), None);
+    }
+
+    #[test]
+    fn largest_seeds() {
+        let output = ::rng::mt19937::Mt19937::new(u32::MAX).next_u32();
+        assert_eq!(super::recover_seed(output, u32::MAX - 10, u32::MAX), Some(u32::MAX));
+
+        let token = super::password_reset_token(&SimulatedClock::new(u64::from(u32::MAX)));
+        assert_eq!(super::find_token_seed(&token, u64::MAX, 10), Some(u64::MAX));
+    }
+}
@@ -30,6 +30,11 @@ pub fn hamming_distance(input1: &[u8], input2: &[u8]) -> usize {
 // https://en.wikipedia.org/wiki/Letter_frequency
 static VALUED_CHARS: &'static[&'static str] = &["e", "t", "a", "o", "n"];
 
+// a single column of many ciphertexts is too short for the five letters
+// above to tell candidates apart, so columns also count spaces and the rest
+// of "etaoin shrdlu".
+static COLUMN_CHARS: &'static[&'static str] = &[" ", "e", "t", "a", "o", "i", "n", "s", "h", "r", "d", "l", "u"];
+
 static FAILED_CHARS: &'static[&'static str] = &[
     "\u{0}", "\u{1}", "\u{2}", "\u{3}", "\u{4}", "\u{5}", "\u{6}",
     "\u{7}", "\u{8}", "\u{9}", "\u{12}", "\u{c}", "\u{7f}", "\u{1d}"
@@ -47,6 +52,25 @@ static FAILED_CHARS: &'static[&'static str] = &[
 pub fn score_text(text: &str) -> usize {
     // this scoring function is extremely simple/fragile. that said,
     // it completes the set1 challenges
+    score_with(text, VALUED_CHARS)
+}
+
+/// Scores a column of text, such as every ciphertext's byte at one
+/// position of a fixed-nonce CTR break, by counting the spaces and common
+/// English letters in it. Like `score_text`, text with control characters
+/// scores 0.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(cryptopalslib::score::score_column("t hx"), 3);
+/// assert_eq!(cryptopalslib::score::score_column("th\u{0}"), 0);
+/// ```
+pub fn score_column(text: &str) -> usize {
+    score_with(text, COLUMN_CHARS)
+}
+
+fn score_with(text: &str, valued_chars: &[&str]) -> usize {
     let mut score: usize = 0;
     // if we have any failed characters in the text,
     // it's probably not text we want, so return 0
@@ -58,7 +82,7 @@ pub fn score_text(text: &str) -> usize {
     }
 
     let lowercase = text.to_ascii_lowercase();
-    for &test_char in VALUED_CHARS {
+    for &test_char in valued_chars {
         let test_str = lowercase.replace(test_char, "");
         score += text.len() - test_str.len();
         debug!("input: {:?}, test: {:?}, score increase: {:?}", text, test_str, text.len() - test_str.len());
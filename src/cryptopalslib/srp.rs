@@ -0,0 +1,327 @@
+use bignum::BigUint;
+use error::Error;
+use hash::MerkleDamgard;
+use hash::sha256::Sha256;
+use std::collections::HashMap;
+
+/// The group and multiplier shared by an SRP client and server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameters {
+    pub n: BigUint,
+    pub g: BigUint,
+    pub k: BigUint,
+}
+
+impl Parameters {
+    /// Uses the 1536-bit MODP prime with g = 2, and computes k as SRP-6a
+    /// does: k = H(N | PAD(g)).
+    pub fn new() -> Parameters {
+        let group = ::dh::Group::modp_1536();
+        let k = hash_to_int(&[&group.p.to_bytes_be(), &pad(&group.g, &group.p)]);
+        Parameters { n: group.p, g: group.g, k }
+    }
+}
+
+impl Default for Parameters {
+    fn default() -> Parameters {
+        Parameters::new()
+    }
+}
+
+/// Hashes the concatenation of `parts` with SHA-256, and reads the digest
+/// as a big-endian number.
+fn hash_to_int(parts: &[&[u8]]) -> BigUint {
+    BigUint::from_bytes_be(&Sha256::digest(&parts.concat()))
+}
+
+/// Writes `value` as big-endian bytes, left-padded to the length of `n`.
+fn pad(value: &BigUint, n: &BigUint) -> Vec<u8> {
+    let bytes = value.to_bytes_be();
+    let length = (n.bits() + 7) / 8;
+    let mut output = vec!(0; length.saturating_sub(bytes.len()));
+    output.extend_from_slice(&bytes);
+    output
+}
+
+/// Computes x = H(salt | password). Unlike RFC 5054, the username isn't
+/// mixed in, following the challenges.
+pub fn private_key(salt: &[u8], password: &str) -> BigUint {
+    hash_to_int(&[salt, password.as_bytes()])
+}
+
+/// Computes the verifier v = g ^ x mod N that the server stores instead of
+/// the password.
+pub fn verifier(parameters: &Parameters, salt: &[u8], password: &str) -> BigUint {
+    parameters.g.modpow(&private_key(salt, password), &parameters.n)
+}
+
+/// Computes the scrambling parameter u = H(PAD(A) | PAD(B)).
+fn scrambler(parameters: &Parameters, client_public: &BigUint, server_public: &BigUint) -> BigUint {
+    hash_to_int(&[&pad(client_public, &parameters.n), &pad(server_public, &parameters.n)])
+}
+
+/// Derives the session key K = H(S) from the shared secret.
+pub fn session_key(secret: &BigUint) -> Vec<u8> {
+    Sha256::digest(&secret.to_bytes_be())
+}
+
+/// Computes the proof the client sends to log in: HMAC-SHA256(K, salt).
+pub fn proof(key: &[u8], salt: &[u8]) -> Vec<u8> {
+    ::mac::hmac::<Sha256>(key, salt)
+}
+
+/// A password verifier stored by a server.
+#[derive(Debug, Clone)]
+struct User {
+    salt: Vec<u8>,
+    verifier: BigUint,
+}
+
+/// An SRP-6a server, holding registered users and logins in progress.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::srp::{Parameters, Server};
+///
+/// let parameters = Parameters::new();
+/// let mut server = Server::new(parameters.clone());
+/// server.register("alice@example.com", "hunter2");
+/// assert!(cryptopalslib::srp::login(&mut server, &parameters, "alice@example.com", "hunter2"));
+/// assert!(!cryptopalslib::srp::login(&mut server, &parameters, "alice@example.com", "hunter3"));
+/// ```
+pub struct Server {
+    parameters: Parameters,
+    strict: bool,
+    users: HashMap<String, User>,
+    // the expected proof for each login in progress
+    sessions: HashMap<String, Vec<u8>>,
+}
+
+impl Server {
+    /// Creates a server that, like the one in the challenges, doesn't check
+    /// the client's public key.
+    pub fn new(parameters: Parameters) -> Server {
+        Server { parameters, strict: false, users: HashMap::new(), sessions: HashMap::new() }
+    }
+
+    /// Creates a server that rejects client public keys that are 0 mod N,
+    /// as RFC 5054 requires.
+    pub fn strict(parameters: Parameters) -> Server {
+        Server { strict: true, ..Server::new(parameters) }
+    }
+
+    /// Registers a user with a fresh random salt.
+    pub fn register(&mut self, email: &str, password: &str) {
+        let salt = ::openssl::crypto::rand::rand_bytes(16);
+        let verifier = verifier(&self.parameters, &salt, password);
+        self.users.insert(email.to_string(), User { salt, verifier });
+    }
+
+    /// Starts a login with the client's public key A, returning the salt
+    /// and the server's public key B = kv + g ^ b.
+    pub fn start(&mut self, email: &str, client_public: &BigUint) -> Result<(Vec<u8>, BigUint), Error> {
+        let user = match self.users.get(email) {
+            Some(user) => user.clone(),
+            None => return Err(Error::UnknownUser),
+        };
+        let n = &self.parameters.n;
+        if self.strict && (client_public % n).is_zero() {
+            return Err(Error::InvalidPublicKey);
+        }
+
+        let private = BigUint::random_below(n);
+        let server_public = &(&(&self.parameters.k * &user.verifier) + &self.parameters.g.modpow(&private, n)) % n;
+
+        // S = (A * v ^ u) ^ b
+        let u = scrambler(&self.parameters, client_public, &server_public);
+        let base = &(client_public * &user.verifier.modpow(&u, n)) % n;
+        let key = session_key(&base.modpow(&private, n));
+
+        self.sessions.insert(email.to_string(), proof(&key, &user.salt));
+        Ok((user.salt, server_public))
+    }
+
+    /// Checks the client's proof for a login started with `start`.
+    pub fn verify(&mut self, email: &str, client_proof: &[u8]) -> bool {
+        match self.sessions.remove(email) {
+            Some(expected) => ::mac::constant_time_compare(&expected, client_proof),
+            None => false,
+        }
+    }
+}
+
+/// The client side of an SRP-6a login.
+pub struct Client {
+    parameters: Parameters,
+    password: String,
+    private: BigUint,
+    /// The client's public key A = g ^ a.
+    pub public: BigUint,
+}
+
+impl Client {
+    /// Generates a fresh key pair for a login.
+    pub fn new(parameters: &Parameters, password: &str) -> Client {
+        let private = BigUint::random_below(&parameters.n);
+        let public = parameters.g.modpow(&private, &parameters.n);
+        Client { parameters: parameters.clone(), password: password.to_string(), private, public }
+    }
+
+    /// Computes the login proof from the server's salt and public key.
+    pub fn finish(&self, salt: &[u8], server_public: &BigUint) -> Result<Vec<u8>, Error> {
+        let n = &self.parameters.n;
+        if (server_public % n).is_zero() {
+            return Err(Error::InvalidPublicKey);
+        }
+
+        // S = (B - k * g ^ x) ^ (a + u * x)
+        let x = private_key(salt, &self.password);
+        let u = scrambler(&self.parameters, &self.public, server_public);
+        let subtrahend = &(&self.parameters.k * &self.parameters.g.modpow(&x, n)) % n;
+        let base = &(&(server_public % n) + n) - &subtrahend;
+        let exponent = &self.private + &(&u * &x);
+        let key = session_key(&base.modpow(&exponent, n));
+        Ok(proof(&key, salt))
+    }
+}
+
+/// Runs a full login against the server, returning whether it worked.
+pub fn login(server: &mut Server, parameters: &Parameters, email: &str, password: &str) -> bool {
+    let client = Client::new(parameters, password);
+    let result = server.start(email, &client.public)
+        .and_then(|(salt, server_public)| client.finish(&salt, &server_public));
+    match result {
+        Ok(client_proof) => server.verify(email, &client_proof),
+        Err(_) => false,
+    }
+}
+
+/// The server side of the simplified SRP from challenge 38, where B = g ^ b
+/// doesn't depend on the password and u is a random 128-bit number.
+///
+/// Anything that can stand in for that server, honestly or not, implements
+/// this trait.
+pub trait SimpleServer {
+    /// Starts a login with the client's public key A, returning the salt,
+    /// B and u.
+    fn start(&mut self, email: &str, client_public: &BigUint) -> Result<(Vec<u8>, BigUint, BigUint), Error>;
+
+    /// Checks the client's proof for a login started with `start`.
+    fn verify(&mut self, email: &str, client_proof: &[u8]) -> bool;
+}
+
+/// An honest simplified SRP server.
+pub struct HonestSimpleServer {
+    parameters: Parameters,
+    users: HashMap<String, User>,
+    sessions: HashMap<String, Vec<u8>>,
+}
+
+impl HonestSimpleServer {
+    pub fn new(parameters: Parameters) -> HonestSimpleServer {
+        HonestSimpleServer { parameters, users: HashMap::new(), sessions: HashMap::new() }
+    }
+
+    /// Registers a user with a fresh random salt.
+    pub fn register(&mut self, email: &str, password: &str) {
+        let salt = ::openssl::crypto::rand::rand_bytes(16);
+        let verifier = verifier(&self.parameters, &salt, password);
+        self.users.insert(email.to_string(), User { salt, verifier });
+    }
+}
+
+impl SimpleServer for HonestSimpleServer {
+    fn start(&mut self, email: &str, client_public: &BigUint) -> Result<(Vec<u8>, BigUint, BigUint), Error> {
+        let user = match self.users.get(email) {
+            Some(user) => user.clone(),
+            None => return Err(Error::UnknownUser),
+        };
+        let n = &self.parameters.n;
+        let private = BigUint::random_below(n);
+        let server_public = self.parameters.g.modpow(&private, n);
+        let u = BigUint::from_bytes_be(&::openssl::crypto::rand::rand_bytes(16));
+
+        // S = (A * v ^ u) ^ b
+        let base = &(client_public * &user.verifier.modpow(&u, n)) % n;
+        let key = session_key(&base.modpow(&private, n));
+
+        self.sessions.insert(email.to_string(), proof(&key, &user.salt));
+        Ok((user.salt, server_public, u))
+    }
+
+    fn verify(&mut self, email: &str, client_proof: &[u8]) -> bool {
+        match self.sessions.remove(email) {
+            Some(expected) => ::mac::constant_time_compare(&expected, client_proof),
+            None => false,
+        }
+    }
+}
+
+/// Logs in to a simplified SRP server, returning whether it worked.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::srp::{HonestSimpleServer, Parameters};
+///
+/// let parameters = Parameters::new();
+/// let mut server = HonestSimpleServer::new(parameters.clone());
+/// server.register("bob@example.com", "swordfish");
+/// assert!(cryptopalslib::srp::simple_login(&mut server, &parameters, "bob@example.com", "swordfish"));
+/// ```
+pub fn simple_login<S: SimpleServer>(server: &mut S, parameters: &Parameters, email: &str, password: &str) -> bool {
+    let n = &parameters.n;
+    let private = BigUint::random_below(n);
+    let client_public = parameters.g.modpow(&private, n);
+    let (salt, server_public, u) = match server.start(email, &client_public) {
+        Ok(response) => response,
+        Err(_) => return false,
+    };
+
+    // S = B ^ (a + u * x)
+    let x = private_key(&salt, password);
+    let key = session_key(&server_public.modpow(&(&private + &(&u * &x)), n));
+    server.verify(email, &proof(&key, &salt))
+}
+
+#[cfg(test)]
+mod test {
+    use error::Error;
+    use super::{Client, HonestSimpleServer, Parameters, Server};
+
+    #[test]
+    fn login() {
+        let parameters = Parameters::new();
+        let mut server = Server::new(parameters.clone());
+        server.register("alice@example.com", "correct horse");
+        assert!(super::login(&mut server, &parameters, "alice@example.com", "correct horse"));
+        assert!(!super::login(&mut server, &parameters, "alice@example.com", "battery staple"));
+        assert!(!super::login(&mut server, &parameters, "eve@example.com", "correct horse"));
+    }
+
+    #[test]
+    fn strict_server_rejects_zero_key() {
+        let parameters = Parameters::new();
+        let mut server = Server::strict(parameters.clone());
+        server.register("alice@example.com", "correct horse");
+        assert_eq!(server.start("alice@example.com", &parameters.n).err(), Some(Error::InvalidPublicKey));
+        assert!(super::login(&mut server, &parameters, "alice@example.com", "correct horse"));
+    }
+
+    #[test]
+    fn client_rejects_zero_key() {
+        let parameters = Parameters::new();
+        let client = Client::new(&parameters, "password");
+        assert_eq!(client.finish(b"salt", &parameters.n), Err(Error::InvalidPublicKey));
+    }
+
+    #[test]
+    fn simple_login() {
+        let parameters = Parameters::new();
+        let mut server = HonestSimpleServer::new(parameters.clone());
+        server.register("bob@example.com", "swordfish");
+        assert!(super::simple_login(&mut server, &parameters, "bob@example.com", "swordfish"));
+        assert!(!super::simple_login(&mut server, &parameters, "bob@example.com", "trout"));
+    }
+}
@@ -0,0 +1,164 @@
+use clock::{Clock, SimulatedClock};
+use rng::Rng;
+use rng::mt19937::Mt19937_64;
+use std::cell::RefCell;
+use std::f64::consts::PI;
+
+/// How much measurement noise a `SimulatedTimer` adds, in nanoseconds.
+///
+/// Every measurement gets `base` plus Gaussian jitter with standard
+/// deviation `jitter`. With probability `spike_probability`, `spike` is added
+/// on top, standing in for things like scheduling delays.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoiseModel {
+    pub base: u64,
+    pub jitter: f64,
+    pub spike_probability: f64,
+    pub spike: u64,
+}
+
+impl NoiseModel {
+    /// A model that adds nothing, so measurements are exact.
+    pub fn none() -> NoiseModel {
+        NoiseModel { base: 0, jitter: 0.0, spike_probability: 0.0, spike: 0 }
+    }
+}
+
+/// Times operations against a `SimulatedClock`, adding noise from a
+/// `NoiseModel`. The noise comes from a seeded generator, so a given seed
+/// always gives the same measurements.
+///
+/// Oracles that would sleep or do slow work sleep on the simulated clock
+/// instead, which keeps side-channel tests fast and deterministic.
+///
+/// # Examples
+///
+/// ```
+/// use cryptopalslib::clock::Clock;
+/// use cryptopalslib::timing::{NoiseModel, SimulatedTimer};
+///
+/// let timer = SimulatedTimer::new(NoiseModel::none(), 1);
+/// let (output, elapsed) = timer.measure(|clock| {
+///     clock.sleep_nanos(300);
+///     "done"
+/// });
+/// assert_eq!(output, "done");
+/// assert_eq!(elapsed, 300);
+/// ```
+pub struct SimulatedTimer {
+    clock: SimulatedClock,
+    noise: NoiseModel,
+    rng: RefCell<Mt19937_64>,
+}
+
+impl SimulatedTimer {
+    /// Creates a timer with the given noise, seeding its generator with `seed`.
+    pub fn new(noise: NoiseModel, seed: u64) -> SimulatedTimer {
+        SimulatedTimer { clock: SimulatedClock::new(0), noise, rng: RefCell::new(Mt19937_64::new(seed)) }
+    }
+
+    /// The underlying clock.
+    pub fn clock(&self) -> &SimulatedClock {
+        &self.clock
+    }
+
+    /// Runs `operation`, which may sleep on the clock, and returns its
+    /// output along with the measured time in nanoseconds.
+    pub fn measure<T, F: FnOnce(&SimulatedClock) -> T>(&self, operation: F) -> (T, u64) {
+        let start = self.clock.now_nanos();
+        let output = operation(&self.clock);
+        let noise = self.sample_noise();
+        self.clock.sleep_nanos(noise);
+        (output, self.clock.now_nanos() - start)
+    }
+
+    /// Draws the noise for one measurement. Negative jitter is clamped so
+    /// that time never runs backwards.
+    fn sample_noise(&self) -> u64 {
+        let mut rng = self.rng.borrow_mut();
+        let jitter = gaussian(&mut *rng) * self.noise.jitter;
+        let mut noise = (self.noise.base as f64 + jitter).max(0.0) as u64;
+        if uniform(&mut *rng) < self.noise.spike_probability {
+            noise += self.noise.spike;
+        }
+        noise
+    }
+}
+
+/// Returns a uniformly distributed number in [0, 1).
+fn uniform<R: Rng>(rng: &mut R) -> f64 {
+    // 53 bits is all the precision an f64 has
+    (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Returns a normally distributed number with mean 0 and standard deviation
+/// 1, using the Box-Muller transform.
+fn gaussian<R: Rng>(rng: &mut R) -> f64 {
+    let first = 1.0 - uniform(rng);
+    let second = uniform(rng);
+    (-2.0 * first.ln()).sqrt() * (2.0 * PI * second).cos()
+}
+
+/// Runs `trial` once for each seed in `0..runs`, and returns the fraction
+/// of runs that succeeded.
+///
+/// # Examples
+///
+/// ```
+/// let rate = cryptopalslib::timing::success_rate(10, |seed| seed % 2 == 0);
+/// assert_eq!(rate, 0.5);
+/// ```
+pub fn success_rate<F: FnMut(u64) -> bool>(runs: u64, mut trial: F) -> f64 {
+    if runs == 0 {
+        return 0.0;
+    }
+    let successes = (0..runs).filter(|&seed| trial(seed)).count();
+    successes as f64 / runs as f64
+}
+
+#[cfg(test)]
+mod test {
+    use clock::Clock;
+    use super::{NoiseModel, SimulatedTimer};
+
+    #[test]
+    fn noise_statistics() {
+        let noise = NoiseModel { base: 10000, jitter: 500.0, spike_probability: 0.1, spike: 100000 };
+        let timer = SimulatedTimer::new(noise, 42);
+        let samples: Vec<u64> = (0..10000).map(|_| timer.measure(|_| ()).1).collect();
+
+        let spikes = samples.iter().filter(|&&x| x > 50000).count();
+        assert!(spikes > 900 && spikes < 1100);
+
+        let quiet: Vec<f64> = samples.iter().filter(|&&x| x <= 50000).map(|&x| x as f64).collect();
+        let mean = quiet.iter().sum::<f64>() / quiet.len() as f64;
+        let variance = quiet.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / quiet.len() as f64;
+        assert!((mean - 10000.0).abs() < 50.0);
+        assert!((variance.sqrt() - 500.0).abs() < 25.0);
+    }
+
+    #[test]
+    fn same_seed_same_measurements() {
+        let noise = NoiseModel { base: 100, jitter: 10.0, spike_probability: 0.5, spike: 1000 };
+        let first = SimulatedTimer::new(noise, 7);
+        let second = SimulatedTimer::new(noise, 7);
+        for _ in 0..100 {
+            assert_eq!(first.measure(|_| ()), second.measure(|_| ()));
+        }
+    }
+
+    #[test]
+    fn timing_leak_attack_is_robust() {
+        // a leak on the order of the jitter, plus spikes that dwarf it
+        let noise = NoiseModel { base: 20000, jitter: 500.0, spike_probability: 0.05, spike: 200000 };
+        let secret = [0x5a, 0xc3, 0x17];
+        let rate = super::success_rate(200, |seed| {
+            let timer = SimulatedTimer::new(noise, seed);
+            let output = ::attacks::timing_leak::recover_mac_with(3, 15, ::attacks::timing_leak::median, |guess| {
+                timer.measure(|clock| ::mac::insecure_compare_with(&secret, guess, || clock.sleep_nanos(1000)))
+            });
+            output == secret
+        });
+        assert!(rate >= 0.95, "success rate {}", rate);
+    }
+}
@@ -14,6 +14,12 @@ use std::str;
 /// println!("{} {} {}", score, value, best_string);
 /// ```
 pub fn score_and_xor(decimal_values: Vec<u8>) -> (usize, u8, String) {
+    score_and_xor_with(decimal_values, &::score::score_text)
+}
+
+/// Like `score_and_xor`, but scores each xored string with `score` instead
+/// of `score::score_text`.
+pub fn score_and_xor_with<F: Fn(&str) -> usize>(decimal_values: Vec<u8>, score: &F) -> (usize, u8, String) {
     let mut best_string = String::new();
     let mut best_string_score = 0;
     let mut best_string_value = 0;
@@ -27,10 +33,10 @@ pub fn score_and_xor(decimal_values: Vec<u8>) -> (usize, u8, String) {
         // turn the byte vector into a string
         match str::from_utf8(&decoded_values) {
             Ok(v) => {
-                let score = ::score::score_text(v);
-                if score > best_string_score {
+                let string_score = score(v);
+                if string_score > best_string_score {
                     best_string = v.to_string();
-                    best_string_score = score;
+                    best_string_score = string_score;
                     best_string_value = test_val;
                 }
             }
@@ -40,3 +46,163 @@ pub fn score_and_xor(decimal_values: Vec<u8>) -> (usize, u8, String) {
 
     (best_string_score, best_string_value, best_string)
 }
+
+/// Treats the input as text encrypted with a repeating-key XOR of length
+/// `keysize`, and solves each key byte as a single-byte XOR.
+///
+/// The input is transposed into `keysize` columns, where column `n` holds
+/// every byte whose position is `n` modulo `keysize`. Each column is scored
+/// with `score_and_xor`.
+///
+/// Returns the best key and the decoded columns. Both vectors are empty if
+/// any column couldn't be decoded into valid text.
+///
+/// # Examples
+/// ```
+/// let input: Vec<u8> = "hello world".bytes().zip([1u8, 2].iter().cycle()).map(|(x, y)| x ^ y).collect();
+/// let (key, columns) = cryptopalslib::xor::find_best_key_and_columns(2, &input);
+/// println!("{:?} {:?}", key, columns);
+/// ```
+pub fn find_best_key_and_columns(keysize: usize, bytes: &Vec<u8>) -> (Vec<u8>, Vec<String>) {
+    find_best_key_and_columns_with(keysize, bytes, &::score::score_text)
+}
+
+/// Like `find_best_key_and_columns`, but scores each column with `score`
+/// instead of `score::score_text`.
+pub fn find_best_key_and_columns_with<F: Fn(&str) -> usize>(keysize: usize, bytes: &Vec<u8>, score: &F) -> (Vec<u8>, Vec<String>) {
+    let mut decoded_columns = vec!();
+    let mut best_keys = vec!();
+    for index in 0..keysize {
+        let filtered_bytes: Vec<_> = bytes.iter().enumerate()
+            .filter(
+                |&(x, _)| {
+                    if index > x {
+                        return false
+                    } else {
+                        return (x - index) % keysize == 0
+                    }
+                })
+            .map(|(_, &y)| y)
+            .collect();
+
+        let (_, best_key, string) = score_and_xor_with(filtered_bytes, score);
+
+        // if we get an empty string back, we don't have any valid text,
+        // so throw out this keysize
+        if string == "" {
+            best_keys = vec!();
+            decoded_columns = vec!();
+            break;
+        }
+
+        best_keys.push(best_key);
+        decoded_columns.push(string);
+    }
+    (best_keys, decoded_columns)
+}
+
+/// A guess for a single byte of a keystream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeystreamGuess {
+    /// The guessed keystream byte.
+    pub value: u8,
+    /// How many ciphertext bytes were available at this position.
+    pub samples: usize,
+    /// The fraction of decoded bytes at this position that are spaces or
+    /// common English letters, as counted by `score::score_column`. 0 means
+    /// no byte produced valid text.
+    pub confidence: f32,
+}
+
+/// Recovers the keystream shared by a collection of ciphertexts, such as
+/// the output of CTR mode with a reused nonce.
+///
+/// Every ciphertext is truncated to the length of the shortest one, and the
+/// result is solved as repeating-key XOR, scoring columns with
+/// `score::score_column`.
+/// Positions past the shortest ciphertext are solved one column at a time
+/// using only the ciphertexts that are long enough, so guesses near the end
+/// of the longest ciphertext are based on fewer samples.
+///
+/// Returns one guess per byte of the longest ciphertext.
+///
+/// # Examples
+/// ```
+/// let ciphertexts = vec!(vec!(1, 2, 3), vec!(4, 5));
+/// let guesses = cryptopalslib::xor::break_fixed_nonce(&ciphertexts);
+/// assert_eq!(guesses.len(), 3);
+/// ```
+pub fn break_fixed_nonce(ciphertexts: &[Vec<u8>]) -> Vec<KeystreamGuess> {
+    let common_length = ciphertexts.iter().map(|x| x.len()).min().unwrap_or(0);
+    let longest = ciphertexts.iter().map(|x| x.len()).max().unwrap_or(0);
+
+    let mut truncated = vec!();
+    for ciphertext in ciphertexts {
+        truncated.extend(ciphertext[..common_length].iter().cloned());
+    }
+    let (common_keys, common_columns) = find_best_key_and_columns_with(common_length, &truncated, &::score::score_column);
+
+    let mut guesses = vec!();
+    for index in 0..longest {
+        let column: Vec<u8> = ciphertexts.iter()
+            .filter(|x| x.len() > index)
+            .map(|x| x[index])
+            .collect();
+        let samples = column.len();
+
+        // fall back to solving the column on its own if the truncated
+        // ciphertexts couldn't be solved as a whole
+        let (score, value) = if index < common_keys.len() {
+            (::score::score_column(&common_columns[index]), common_keys[index])
+        } else {
+            let (score, value, _) = score_and_xor_with(column, &::score::score_column);
+            (score, value)
+        };
+
+        guesses.push(KeystreamGuess {
+            value,
+            samples,
+            confidence: score as f32 / samples as f32,
+        });
+    }
+    guesses
+}
+
+#[cfg(test)]
+mod test {
+
+    #[test]
+    fn break_fixed_nonce() {
+        let text = "Bletchey Park rejoices in the fact that, until fairly recently, it was probably \
+            Britain's best kept secret. This is because of the secrecy surrounding all the activities \
+            carried on here during World War Two was of vital importance to our national security and \
+            ultimate victory. I wanted to write certain functions myself, even though they can already \
+            be found in Rust. I haven't put effort into optimizing the code, so I wouldn't expect it to \
+            be as performant as possible. I was focused on finding solutions for the challenges, so I \
+            haven't necessarily revisited the code to clean it up.";
+        let keystream: Vec<u8> = (0..64).map(|x: u8| x.wrapping_mul(37).wrapping_add(11)).collect();
+
+        // split the text into lines of a few different lengths
+        let mut ciphertexts = vec!();
+        let mut remaining = text.as_bytes();
+        for &length in [24, 29, 26, 31].iter().cycle() {
+            if remaining.len() < length {
+                break;
+            }
+            let (line, rest) = remaining.split_at(length);
+            ciphertexts.push(line.iter().zip(keystream.iter()).map(|(x, y)| x ^ y).collect::<Vec<u8>>());
+            remaining = rest;
+        }
+
+        let guesses = super::break_fixed_nonce(&ciphertexts);
+        assert_eq!(guesses.len(), 31);
+        assert_eq!(guesses[0].samples, ciphertexts.len());
+        assert_eq!(guesses[30].samples, ciphertexts.len() / 4);
+
+        // with this few samples per column, the scoring can still pick the
+        // wrong byte now and then, so only check most of the truncated section
+        let correct = (0..24).filter(|&x| guesses[x].value == keystream[x]).count();
+        assert!(correct >= 22);
+        assert!(guesses.iter().take(24).all(|x| x.confidence > 0.0));
+    }
+}
@@ -113,7 +113,7 @@ fn break_repeating_key_xor(bytes: Vec<u8>) -> (String, String) {
 
 		// find the best key and the corresponding decoded column for
 		// each byte of the keysize.
-		let (best_keys, decoded_columns) = find_best_key_and_columns(keysize, &bytes);
+		let (best_keys, decoded_columns) = cryptopalslib::xor::find_best_key_and_columns(keysize, &bytes);
 
 
 		// if we've got something in the output string list,
@@ -141,38 +141,6 @@ fn break_repeating_key_xor(bytes: Vec<u8>) -> (String, String) {
 	("".to_string(), "".to_string())
 }
 
-fn find_best_key_and_columns(keysize: usize, bytes: &Vec<u8>) -> (Vec<u8>, Vec<String>) {
-	let mut decoded_columns = vec!();
-	let mut best_keys = vec!();
-	for index in 0..keysize {
-		let filtered_bytes: Vec<_> = bytes.iter().enumerate()
-			.filter(
-				|&(x, _)| {
-					if index > x {
-						return false
-					} else {
-						return (x - index) % keysize == 0
-					}
-				})
-			.map(|(_, &y)| y)
-			.collect();
-
-		let (_, best_key, string) = cryptopalslib::xor::score_and_xor(filtered_bytes);
-
-		// if we get an empty string back, we don't have any valid text,
-		// so throw out this keysize
-		if string == "" {
-			best_keys = vec!();
-			decoded_columns = vec!();
-			break;
-		}
-
-		best_keys.push(best_key);
-		decoded_columns.push(string);
-	}
-	(best_keys, decoded_columns)
-}
-
 fn rank_keylengths(bytes: &Vec<u8>) -> Vec<(f32, usize)> {
 	let mut results: Vec<(f32, usize)> = Vec::new();
 	for keysize in 2..cmp::min(40, (bytes.len() / 3)) {
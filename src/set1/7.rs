@@ -12,10 +12,11 @@
 
 #[macro_use]
 extern crate log;
-extern crate openssl;
 extern crate cryptopalslib;
 
-use std::str;
+use cryptopalslib::aes::decrypt_base64_aes_ecb_128;
+#[cfg(not(test))]
+use cryptopalslib::error::Error;
 
 #[cfg(not(test))]
 use std::env;
@@ -42,12 +43,14 @@ fn main() {
 		None => panic!("No input argument given")
 	};
 
-	let output = decrypt_aes_ecb_128_file("YELLOW SUBMARINE", &arg);
-	println!("output: {:?}", output);
+	match decrypt_aes_ecb_128_file("YELLOW SUBMARINE", &arg) {
+		Ok(output) => println!("output: {:?}", output),
+		Err(e) => println!("Couldn't decrypt the file: {}", e),
+	}
 }
 
 #[cfg(not(test))]
-fn decrypt_aes_ecb_128_file(key: &str, path: &str) -> String {
+fn decrypt_aes_ecb_128_file(key: &str, path: &str) -> Result<String, Error> {
 	let path = Path::new(path);
 	let file = BufReader::new(File::open(&path).unwrap());
 	let lines: Vec<_> = file.lines()
@@ -57,18 +60,6 @@ fn decrypt_aes_ecb_128_file(key: &str, path: &str) -> String {
 	decrypt_base64_aes_ecb_128(key, lines)
 }
 
-fn decrypt_base64_aes_ecb_128(key: &str, lines: Vec<String>) -> String {
-	let base64_decoded = cryptopalslib::convert::base64_lines_to_hex(&lines);
-	let nums = cryptopalslib::convert::hex_string_to_decimal_pairs(&base64_decoded);
-
-	let t = openssl::crypto::symm::decrypt(openssl::crypto::symm::Type::AES_128_ECB, key.as_bytes(), vec!(), &nums);
-
-	match str::from_utf8(&t) {
-		Ok(s) => return s.to_string(),
-		Err(_) => panic!("Result couldn't be converted to u8")
-	}
-}
-
 #[cfg(test)]
 mod set1challenge7 {
 
@@ -79,7 +70,7 @@ mod set1challenge7 {
 		let key = "TESTTESTTESTTEST";
 
 		let output = super::decrypt_base64_aes_ecb_128(key, vec!(input));
-		assert_eq!(output, original);
+		assert_eq!(output, Ok(original.to_string()));
 	}
 
 }
\ No newline at end of file
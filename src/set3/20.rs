@@ -0,0 +1,118 @@
+// Break fixed-nonce CTR statistically
+// In this file (20.txt) find a similar set of Base64'd plaintext. Do with them
+// exactly what you did with the first, but solve the problem differently.
+
+// Instead of making spot guesses at to known plaintext, treat the collection
+// of ciphertexts the same way you would repeating-key XOR.
+
+// Obviously, CTR encryption appears different from repeated-key XOR, but with
+// a fixed nonce they are effectively the same thing.
+
+// To exploit this: take your collection of ciphertexts and truncate them to a
+// common length (the length of the smallest ciphertext will work).
+
+// Solve the resulting concatenation of ciphertexts as if for repeating- key
+// XOR, with a key size of the length of the ciphertext you XOR'd.
+
+extern crate openssl;
+extern crate cryptopalslib;
+
+use cryptopalslib::xor::KeystreamGuess;
+
+#[cfg(not(test))]
+use std::env;
+#[cfg(not(test))]
+use std::io::BufReader;
+#[cfg(not(test))]
+use std::fs::File;
+#[cfg(not(test))]
+use std::path::Path;
+#[cfg(not(test))]
+use std::io::prelude::*;
+
+#[cfg(not(test))]
+fn main() {
+
+	println!("Breaking fixed-nonce CTR...");
+
+	if env::args().count() < 2 {
+		panic!("Must pass a file to decode")
+	}
+
+	let arg = match env::args().nth(1) {
+		Some(s) => s,
+		None => panic!("No input argument given")
+	};
+
+	let path = Path::new(&arg);
+	let file = BufReader::new(File::open(&path).unwrap());
+	let lines: Vec<String> = file.lines()
+		.map(|x| x.unwrap().trim().to_string())
+		.filter(|x| x.len() > 0)
+		.collect();
+
+	let plaintexts: Vec<Vec<u8>> = lines.iter()
+		.map(|x| cryptopalslib::convert::hex_string_to_decimal_pairs(&cryptopalslib::convert::base64_to_hex(x)))
+		.collect();
+	let ciphertexts = encrypt_with_fixed_nonce(&plaintexts);
+	let guesses = cryptopalslib::xor::break_fixed_nonce(&ciphertexts);
+
+	for ciphertext in ciphertexts.iter() {
+		println!("{:?}", String::from_utf8_lossy(&apply_keystream(ciphertext, &guesses)));
+	}
+
+	println!("keystream confidence:");
+	for (index, guess) in guesses.iter().enumerate() {
+		println!("{:>3}: {:>3} ({} samples, {:.2})", index, guess.value, guess.samples, guess.confidence);
+	}
+}
+
+/// Encrypts each plaintext under CTR with the same random key and a nonce of 0.
+fn encrypt_with_fixed_nonce(plaintexts: &[Vec<u8>]) -> Vec<Vec<u8>> {
+	let key = openssl::crypto::rand::rand_bytes(16);
+	plaintexts.iter()
+		.map(|x| cryptopalslib::aes::ctr(&key, 0, x))
+		.collect()
+}
+
+fn apply_keystream(ciphertext: &[u8], guesses: &[KeystreamGuess]) -> Vec<u8> {
+	ciphertext.iter().zip(guesses.iter()).map(|(x, y)| x ^ y.value).collect()
+}
+
+#[cfg(test)]
+mod set3challenge20 {
+
+	#[test]
+	fn break_fixed_nonce_ctr() {
+		let plaintexts: Vec<Vec<u8>> = vec!(
+			"Very rough solutions for the Cryptopals series of crypto challenges",
+			"Common functions are located in the library folder of the repo",
+			"and binaries for individual challenges are located in set folders",
+			"I wanted to write certain functions myself, even though they can",
+			"already be found in Rust, for example base64 conversion can be found",
+			"I haven't put effort into optimizing the code, so I wouldn't expect",
+			"it to be as performant as possible. I was focused on finding solutions",
+			"for the challenges, so I haven't necessarily revisited the code to",
+			"clean it up. I'd also like for this code to be idiomatic Rust, but",
+			"I'm still learning what that means, so I'd appreciate any suggestions",
+			"for how to make it better. Head over to the site and install Rust",
+			"Clone the repo and change directory to the root of the repository",
+			"To run tests, run the test command, and to run programs for each one",
+			"of the individual challenges, run the binary for the set and number",
+			"Bletchey Park rejoices in the fact that, until fairly recently, it",
+			"was probably the best kept secret in all of Britain during the war",
+		).iter().map(|x| x.as_bytes().to_vec()).collect();
+
+		let ciphertexts = super::encrypt_with_fixed_nonce(&plaintexts);
+		let guesses = cryptopalslib::xor::break_fixed_nonce(&ciphertexts);
+		let output = super::apply_keystream(&ciphertexts[0], &guesses);
+
+		// ties between candidate keystream bytes depend on the random key,
+		// so allow a few bytes to come out wrong
+		let correct = output.iter().zip(plaintexts[0].iter())
+			.filter(|&(x, y)| x.to_ascii_lowercase() == y.to_ascii_lowercase())
+			.count();
+		assert_eq!(output.len(), plaintexts[0].len());
+		assert!(correct * 10 >= output.len() * 8);
+	}
+}
@@ -0,0 +1,82 @@
+// Crack an MT19937 seed
+// Make sure your MT19937 accepts an integer seed value. Test it (verify that
+// you're getting the same sequence of outputs given a seed).
+
+// Write a routine that performs the following operation:
+
+// Wait a random number of seconds between, I don't know, 40 and 1000.
+// Seeds the RNG with the current Unix timestamp
+// Waits a random number of seconds again.
+// Returns the first 32 bit output of the RNG.
+
+// You get the idea. Go get coffee while it runs. Or just simulate the
+// passage of time, although you're missing some of the fun of this exercise
+// if you do that.
+
+// From the 32 bit RNG output, discover the seed.
+
+extern crate openssl;
+extern crate cryptopalslib;
+
+use cryptopalslib::clock::Clock;
+use cryptopalslib::rng::Rng;
+
+#[cfg(not(test))]
+use cryptopalslib::clock::SystemClock;
+
+#[cfg(not(test))]
+fn main() {
+	println!("Generating a time-seeded output. This will take a while...");
+
+	let clock = SystemClock;
+	let (output, _) = time_seeded_output(&clock);
+	println!("output: {:?}", output);
+
+	match crack_time_seed(&clock, output) {
+		Some(seed) => println!("seed: {:?}", seed),
+		None => println!("Couldn't find the seed")
+	}
+}
+
+/// Returns a random number of seconds between 40 and 1000.
+fn random_wait() -> u64 {
+	let bytes = openssl::crypto::rand::rand_bytes(2);
+	40 + ((bytes[0] as u64) << 8 | bytes[1] as u64) % 961
+}
+
+/// Waits, seeds MT19937 with the time, waits again, and returns the first
+/// output along with the seed that was used, so it can be checked.
+fn time_seeded_output<C: Clock>(clock: &C) -> (u32, u32) {
+	clock.sleep(random_wait());
+	let seed = clock.now() as u32;
+	let mut rng = cryptopalslib::rng::seed::seed_from_time(clock);
+	clock.sleep(random_wait());
+	(rng.next_u32(), seed)
+}
+
+/// Looks for the seed in the last two thousand seconds, which covers both
+/// of the waits in `time_seeded_output`.
+fn crack_time_seed<C: Clock>(clock: &C, output: u32) -> Option<u32> {
+	let now = clock.now() as u32;
+	cryptopalslib::rng::seed::recover_seed(output, now.saturating_sub(2000), now)
+}
+
+#[cfg(test)]
+mod set3challenge22 {
+	use cryptopalslib::clock::SimulatedClock;
+
+	#[test]
+	fn crack_time_seed() {
+		let clock = SimulatedClock::new(1500000000);
+		let (output, seed) = super::time_seeded_output(&clock);
+		assert_eq!(super::crack_time_seed(&clock, output), Some(seed));
+	}
+
+	#[test]
+	fn crack_time_seed_near_epoch() {
+		// the search window starts before time 0 here
+		let clock = SimulatedClock::new(0);
+		let (output, seed) = super::time_seeded_output(&clock);
+		assert_eq!(super::crack_time_seed(&clock, output), Some(seed));
+	}
+}
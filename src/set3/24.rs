@@ -0,0 +1,79 @@
+// Create the MT19937 stream cipher and break it
+// You can create a trivial stream cipher out of any PRNG; use it to generate
+// a sequence of 8 bit outputs and call those outputs a keystream. XOR each
+// byte of plaintext with each successive byte of keystream.
+
+// Write the function that does this for MT19937 using a 16-bit seed. Verify
+// that you can encrypt and decrypt properly. This code should look similar
+// to your CTR code.
+
+// Use your function to encrypt a known plaintext (say, 14 consecutive 'A'
+// characters) prefixed by a random number of random characters.
+
+// From the ciphertext, recover the "key" (the 16 bit seed).
+
+// Use the same idea to generate a random "password reset token" using
+// MT19937 seeded from the current time.
+
+// Write a function to check if any given password token is actually the
+// product of an MT19937 PRNG seeded with the current time.
+
+extern crate openssl;
+extern crate cryptopalslib;
+
+#[cfg(not(test))]
+use cryptopalslib::clock::{Clock, SystemClock};
+
+static KNOWN_PLAINTEXT: &'static [u8] = b"AAAAAAAAAAAAAA";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 3, Challenge 24");
+
+	let (key, ciphertext) = encrypt_with_random_prefix();
+	println!("ciphertext: {:?}", ciphertext);
+	match cryptopalslib::rng::seed::recover_cipher_key(&ciphertext, KNOWN_PLAINTEXT) {
+		Some(recovered) => println!("key: {:?}, recovered: {:?}", key, recovered),
+		None => println!("Couldn't recover the key")
+	}
+
+	let clock = SystemClock;
+	let token = cryptopalslib::rng::seed::password_reset_token(&clock);
+	println!("token: {:?}", token);
+	match cryptopalslib::rng::seed::find_token_seed(&token, clock.now(), 3600) {
+		Some(seed) => println!("token was seeded with the time {:?}", seed),
+		None => println!("token wasn't seeded with the time")
+	}
+}
+
+/// Encrypts the known plaintext, with a random prefix of 5 to 20 bytes, under
+/// a random 16-bit key.
+fn encrypt_with_random_prefix() -> (u16, Vec<u8>) {
+	let random = openssl::crypto::rand::rand_bytes(3);
+	let key = (random[0] as u16) << 8 | random[1] as u16;
+	let mut plaintext = openssl::crypto::rand::rand_bytes(5 + random[2] as usize % 16);
+	plaintext.extend_from_slice(KNOWN_PLAINTEXT);
+	(key, cryptopalslib::rng::seed::mt19937_cipher(key, &plaintext))
+}
+
+#[cfg(test)]
+mod set3challenge24 {
+	use cryptopalslib::clock::SimulatedClock;
+
+	#[test]
+	fn recover_cipher_key() {
+		let (key, ciphertext) = super::encrypt_with_random_prefix();
+		let recovered = cryptopalslib::rng::seed::recover_cipher_key(&ciphertext, super::KNOWN_PLAINTEXT);
+		assert_eq!(recovered, Some(key));
+	}
+
+	#[test]
+	fn detect_time_seeded_token() {
+		let clock = SimulatedClock::new(1500000000);
+		let token = cryptopalslib::rng::seed::password_reset_token(&clock);
+		assert_eq!(cryptopalslib::rng::seed::find_token_seed(&token, 1500000100, 3600), Some(1500000000));
+
+		let random_token = openssl::crypto::rand::rand_bytes(16);
+		assert_eq!(cryptopalslib::rng::seed::find_token_seed(&random_token, 1500000100, 3600), None);
+	}
+}
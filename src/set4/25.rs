@@ -0,0 +1,93 @@
+// Break "random access read/write" AES CTR
+// Back to CTR. Encrypt the recovered plaintext from this file (the ECB
+// exercise) under CTR with a random key (for this exercise the key should be
+// unknown to you, but hold on to it).
+
+// Now, write the code that allows you to "seek" into the ciphertext, decrypt,
+// and re-encrypt with different plaintext. Expose this as a function, like,
+// "edit(ciphertext, key, offset, newtext)".
+
+// Imagine the "edit" function was exposed to attackers by means of an API
+// call that didn't reveal the key or the original plaintext; the attacker
+// has the ciphertext and controls the offset and "new text".
+
+// Recover the original plaintext.
+
+extern crate openssl;
+extern crate cryptopalslib;
+
+#[cfg(not(test))]
+use std::env;
+#[cfg(not(test))]
+use std::io::BufReader;
+#[cfg(not(test))]
+use std::fs::File;
+#[cfg(not(test))]
+use std::path::Path;
+#[cfg(not(test))]
+use std::io::prelude::*;
+
+#[cfg(not(test))]
+fn main() {
+
+	println!("Decoding...");
+
+	if env::args().count() < 2 {
+		panic!("Must pass a file to decode")
+	}
+
+	let arg = match env::args().nth(1) {
+		Some(s) => s,
+		None => panic!("No input argument given")
+	};
+
+	let path = Path::new(&arg);
+	let file = BufReader::new(File::open(&path).unwrap());
+	let lines: Vec<String> = file.lines()
+		.map(|x| x.unwrap())
+		.collect();
+
+	let plaintext = match cryptopalslib::aes::decrypt_base64_aes_ecb_128("YELLOW SUBMARINE", lines) {
+		Ok(plaintext) => plaintext,
+		Err(e) => panic!("Couldn't decrypt the file: {}", e),
+	};
+	let (key, ciphertext) = encrypt_with_random_key(plaintext.as_bytes());
+
+	// the attacker only gets to call edit, not look at the key
+	let output = recover_plaintext(&ciphertext, |x, offset, newtext| cryptopalslib::aes::edit(x, &key, offset, newtext));
+	println!("output: {:?}", String::from_utf8_lossy(&output));
+}
+
+/// Encrypts the plaintext under CTR with a random key.
+fn encrypt_with_random_key(plaintext: &[u8]) -> (Vec<u8>, Vec<u8>) {
+	let key = openssl::crypto::rand::rand_bytes(16);
+	let ciphertext = cryptopalslib::aes::ctr(&key, 0, plaintext);
+	(key, ciphertext)
+}
+
+/// Overwrites the whole ciphertext with zeros through the edit function. The
+/// result is the raw keystream, which decrypts the original ciphertext.
+fn recover_plaintext<F: Fn(&[u8], usize, &[u8]) -> Vec<u8>>(ciphertext: &[u8], edit: F) -> Vec<u8> {
+	let keystream = edit(ciphertext, 0, &vec!(0; ciphertext.len()));
+	ciphertext.iter().zip(keystream.iter()).map(|(x, y)| x ^ y).collect()
+}
+
+#[cfg(test)]
+mod set4challenge25 {
+	use std::io::prelude::*;
+	use std::io::BufReader;
+	use std::fs::File;
+
+	#[test]
+	fn recover_plaintext() {
+		let file = BufReader::new(File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/src/set1/7.txt")).unwrap());
+		let lines: Vec<String> = file.lines().map(|x| x.unwrap()).collect();
+		let plaintext = cryptopalslib::aes::decrypt_base64_aes_ecb_128("YELLOW SUBMARINE", lines).unwrap();
+		assert!(plaintext.starts_with("I'm back and I'm ringin' the bell"));
+
+		let (key, ciphertext) = super::encrypt_with_random_key(plaintext.as_bytes());
+		assert!(&ciphertext[..] != plaintext.as_bytes());
+		let output = super::recover_plaintext(&ciphertext, |x, offset, newtext| cryptopalslib::aes::edit(x, &key, offset, newtext));
+		assert_eq!(output, plaintext.as_bytes());
+	}
+}
@@ -0,0 +1,104 @@
+// Recover the key from CBC with IV=Key
+// Take your code from the CBC exercise and modify it so that it repurposes
+// the key for CBC encryption as the IV.
+
+// Applications sometimes use the key as an IV on the auspices that both the
+// sender and the receiver have to know the key already, and can save some
+// space by using it as both a key and an IV.
+
+// Using the key as an IV is insecure; an attacker that can modify ciphertext
+// in flight can get the receiver to decrypt a value that will reveal the key.
+
+// The CBC code from exercise 16 encrypts a URL string. Verify each byte of
+// the plaintext for ASCII compliance (ie, look for high-ASCII values).
+// Noncompliant messages should raise an exception or return an error that
+// includes the decrypted plaintext (this happens all the time in real
+// systems, for what it's worth).
+
+// Use your code to encrypt a message that is at least 3 blocks long:
+
+// AES-CBC(P_1, P_2, P_3) -> C_1, C_2, C_3
+
+// Modify the message (you are now the attacker):
+
+// C_1, C_2, C_3 -> C_1, 0, C_1
+
+// Decrypt the message (you are now the receiver) and raise the appropriate
+// error if high-ASCII is found.
+
+// As the attacker, recovering the plaintext from the error, extract the key:
+
+// P'_1 XOR P'_3
+
+extern crate openssl;
+extern crate cryptopalslib;
+
+use cryptopalslib::error::Error;
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 4, Challenge 27");
+
+	let key = openssl::crypto::rand::rand_bytes(16);
+	let ciphertext = encrypt_with_key_as_iv(&key, b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon");
+
+	match recover_key(&ciphertext, |x| receive(&key, x)) {
+		Some(recovered) => println!("key: {:?}, recovered: {:?}", key, recovered),
+		None => println!("Couldn't recover the key")
+	}
+}
+
+fn encrypt_with_key_as_iv(key: &[u8], plaintext: &[u8]) -> Vec<u8> {
+	cryptopalslib::aes::cbc_encrypt(key, key, plaintext)
+}
+
+/// Decrypts a message, returning the plaintext in an error if any of it is
+/// high-ASCII.
+fn receive(key: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, Error> {
+	let plaintext = cryptopalslib::aes::cbc_decrypt(key, key, ciphertext)?;
+	if plaintext.iter().any(|&x| x > 127) {
+		return Err(Error::NonAscii(plaintext));
+	}
+	Ok(plaintext)
+}
+
+/// Sends C_1, 0, C_1 and recovers the key from the error. The last two blocks
+/// of the original ciphertext are tacked on the end so that the padding
+/// still checks out.
+fn recover_key<F: Fn(&[u8]) -> Result<Vec<u8>, Error>>(ciphertext: &[u8], receive: F) -> Option<Vec<u8>> {
+	if ciphertext.len() < 48 {
+		panic!("Need a ciphertext at least 3 blocks long");
+	}
+
+	let mut modified = ciphertext[..16].to_vec();
+	modified.extend_from_slice(&[0; 16]);
+	modified.extend_from_slice(&ciphertext[..16]);
+	modified.extend_from_slice(&ciphertext[ciphertext.len() - 32..]);
+
+	match receive(&modified) {
+		Err(Error::NonAscii(plaintext)) => {
+			Some(plaintext[..16].iter().zip(plaintext[32..48].iter()).map(|(x, y)| x ^ y).collect())
+		},
+		_ => None
+	}
+}
+
+#[cfg(test)]
+mod set4challenge27 {
+	use cryptopalslib::error::Error;
+
+	#[test]
+	fn receive_rejects_high_ascii() {
+		let key = b"YELLOW SUBMARINE";
+		let ciphertext = super::encrypt_with_key_as_iv(key, b"caf\xc3\xa9");
+		assert_eq!(super::receive(key, &ciphertext), Err(Error::NonAscii(b"caf\xc3\xa9".to_vec())));
+	}
+
+	#[test]
+	fn recover_key() {
+		let key = openssl::crypto::rand::rand_bytes(16);
+		let ciphertext = super::encrypt_with_key_as_iv(&key, b"this message is at least three blocks long, with a tail");
+		let recovered = super::recover_key(&ciphertext, |x| super::receive(&key, x));
+		assert_eq!(recovered, Some(key));
+	}
+}
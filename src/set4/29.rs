@@ -0,0 +1,81 @@
+// Break a SHA-1 keyed MAC using length extension
+// Secret-prefix SHA-1 MACs are trivially breakable.
+
+// The attack on secret-prefix SHA1 relies on the fact that you can take the
+// ouput of SHA-1 and use it as a new starting point for SHA-1, thus taking an
+// arbitrary SHA-1 hash and "feeding it more data".
+
+// Since the key precedes the data in secret-prefix, any additional data you
+// feed the SHA-1 hash in this fashion will appear to have been hashed with
+// the secret key.
+
+// To carry out the attack, you'll need to account for the fact that SHA-1 is
+// "padded" with the bit-length of the message; your forged message will need
+// to include that padding. We call this "glue padding". The final message
+// you actually forge will be:
+
+// SHA1(key || original-message || glue-padding || new-message)
+
+// (where the final padding on the whole constructed message is implied)
+
+// Using this attack, generate a secret-prefix MAC under a secret key (choose
+// a random word from /usr/share/dict/words or something) of the string:
+
+// "comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon"
+
+// Forge a variant of this message that ends with ";admin=true".
+
+extern crate openssl;
+extern crate cryptopalslib;
+
+use cryptopalslib::attacks::length_extension::Forgery;
+use cryptopalslib::hash::sha1::Sha1;
+
+static MESSAGE: &'static [u8] = b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 4, Challenge 29");
+
+	let key = random_key();
+	match forge_admin(&key) {
+		Some(forgery) => {
+			println!("key length: {:?}", forgery.key_length);
+			println!("message: {:?}", String::from_utf8_lossy(&forgery.message));
+			println!("mac: {:?}", cryptopalslib::convert::decimals_to_hex_string(forgery.mac));
+		},
+		None => println!("Couldn't forge a MAC")
+	}
+}
+
+/// Returns a random key between 1 and 32 bytes long.
+fn random_key() -> Vec<u8> {
+	let length = 1 + openssl::crypto::rand::rand_bytes(1)[0] as usize % 32;
+	openssl::crypto::rand::rand_bytes(length)
+}
+
+/// Checks a message's MAC, and whether the message grants admin access.
+fn is_admin(key: &[u8], message: &[u8], mac: &[u8]) -> bool {
+	let admin = b";admin=true";
+	cryptopalslib::mac::secret_prefix::<Sha1>(key, message) == mac
+		&& message.windows(admin.len()).any(|x| x == admin)
+}
+
+fn forge_admin(key: &[u8]) -> Option<Forgery> {
+	let mac = cryptopalslib::mac::secret_prefix::<Sha1>(key, MESSAGE);
+	cryptopalslib::attacks::length_extension::forge_with_oracle::<Sha1, _>(
+		&mac, MESSAGE, b";admin=true", 0..64, |message, mac| is_admin(key, message, mac))
+}
+
+#[cfg(test)]
+mod set4challenge29 {
+
+	#[test]
+	fn forge_admin() {
+		let key = super::random_key();
+		let forgery = super::forge_admin(&key).unwrap();
+		assert_eq!(forgery.key_length, key.len());
+		assert!(super::is_admin(&key, &forgery.message, &forgery.mac));
+		assert!(!super::is_admin(&key, super::MESSAGE, &forgery.mac));
+	}
+}
@@ -0,0 +1,59 @@
+// Break an MD4 keyed MAC using length extension
+// Second verse, same as the first, but use MD4 instead of SHA-1. Having done
+// this attack once against SHA-1, the MD4 variant should take much less time;
+// mostly just the time you'll spend Googling for an implementation of MD4.
+
+extern crate openssl;
+extern crate cryptopalslib;
+
+use cryptopalslib::attacks::length_extension::Forgery;
+use cryptopalslib::hash::md4::Md4;
+
+static MESSAGE: &'static [u8] = b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 4, Challenge 30");
+
+	let key = random_key();
+	match forge_admin(&key) {
+		Some(forgery) => {
+			println!("key length: {:?}", forgery.key_length);
+			println!("message: {:?}", String::from_utf8_lossy(&forgery.message));
+			println!("mac: {:?}", cryptopalslib::convert::decimals_to_hex_string(forgery.mac));
+		},
+		None => println!("Couldn't forge a MAC")
+	}
+}
+
+/// Returns a random key between 1 and 32 bytes long.
+fn random_key() -> Vec<u8> {
+	let length = 1 + openssl::crypto::rand::rand_bytes(1)[0] as usize % 32;
+	openssl::crypto::rand::rand_bytes(length)
+}
+
+/// Checks a message's MAC, and whether the message grants admin access.
+fn is_admin(key: &[u8], message: &[u8], mac: &[u8]) -> bool {
+	let admin = b";admin=true";
+	cryptopalslib::mac::secret_prefix::<Md4>(key, message) == mac
+		&& message.windows(admin.len()).any(|x| x == admin)
+}
+
+fn forge_admin(key: &[u8]) -> Option<Forgery> {
+	let mac = cryptopalslib::mac::secret_prefix::<Md4>(key, MESSAGE);
+	cryptopalslib::attacks::length_extension::forge_with_oracle::<Md4, _>(
+		&mac, MESSAGE, b";admin=true", 0..64, |message, mac| is_admin(key, message, mac))
+}
+
+#[cfg(test)]
+mod set4challenge30 {
+
+	#[test]
+	fn forge_admin() {
+		let key = super::random_key();
+		let forgery = super::forge_admin(&key).unwrap();
+		assert_eq!(forgery.key_length, key.len());
+		assert!(super::is_admin(&key, &forgery.message, &forgery.mac));
+		assert!(!super::is_admin(&key, super::MESSAGE, &forgery.mac));
+	}
+}
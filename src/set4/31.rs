@@ -0,0 +1,92 @@
+// Implement and break HMAC-SHA1 with an artificial timing leak
+// The psuedocode on Wikipedia should be enough. HMAC is very easy.
+
+// Using the web framework of your choosing (Sinatra, web.py, whatever),
+// write a tiny application that has a URL that takes a "file" argument and a
+// "signature" argument, like so:
+
+// http://localhost:9000/test?file=foo&signature=46b4ec586117154dacd49d664e5d63fdc88efb51
+
+// Have the server generate an HMAC key, and then verify that the "signature"
+// on incoming requests is valid for "file", using the "==" operator to
+// compare the valid MAC for a file with the "signature" parameter (in other
+// words, verify the HMAC the way any normal programmer would verify it).
+
+// Write a function, call it "insecure_compare", that implements the ==
+// operation by doing byte-at-a-time comparisons with early exit (ie, return
+// false at the first non-matching byte).
+
+// In the loop for "insecure_compare", add a 50ms sleep (sleep 50ms after
+// each byte).
+
+// Use your "insecure_compare" function to verify the HMACs on incoming
+// requests, and test that the whole contraption works. Return a 500 if the
+// MAC is invalid, and a 200 if it's OK.
+
+// Using the timing leak in this application, write a program that discovers
+// the valid MAC for any file.
+
+extern crate openssl;
+extern crate cryptopalslib;
+
+use cryptopalslib::hmac_server::request;
+#[cfg(not(test))]
+use cryptopalslib::hmac_server::HmacServer;
+use std::net::SocketAddr;
+#[cfg(not(test))]
+use std::time::Duration;
+
+static FILE: &'static str = "foo";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 4, Challenge 31");
+
+	let key = openssl::crypto::rand::rand_bytes(16);
+	let server = HmacServer::start(key, Duration::from_millis(50)).unwrap();
+	// the leak is big enough that one sample per guess does the job
+	let mac = recover_mac(server.address(), FILE, 20, 1);
+	println!("mac: {:?}", cryptopalslib::convert::decimals_to_hex_string(mac.clone()));
+	println!("status: {:?}", request(server.address(), FILE, &mac).unwrap().0);
+}
+
+/// Recovers the first `length` bytes of the HMAC-SHA1 of `file` by timing
+/// requests to the server. Guesses are padded with zeros to the full 20
+/// bytes, since the server rejects shorter signatures without comparing
+/// them.
+fn recover_mac(address: SocketAddr, file: &str, length: usize, samples: usize) -> Vec<u8> {
+	cryptopalslib::attacks::timing_leak::recover_mac(length, samples, |guess| {
+		let mut signature = guess.to_vec();
+		signature.resize(20, 0);
+		let (status, elapsed) = request(address, file, &signature).unwrap();
+		(status == 200, elapsed.as_secs() * 1_000_000_000 + elapsed.subsec_nanos() as u64)
+	})
+}
+
+#[cfg(test)]
+mod set4challenge31 {
+	use cryptopalslib::hash::sha1::Sha1;
+	use cryptopalslib::hmac_server::{request, HmacServer};
+	use std::time::Duration;
+
+	#[test]
+	fn first_byte_leaks() {
+		let server = HmacServer::start(b"key".to_vec(), Duration::from_millis(20)).unwrap();
+		let mut guess = vec!(0; 20);
+		guess[0] = cryptopalslib::mac::hmac::<Sha1>(b"key", super::FILE.as_bytes())[0];
+		let (_, right) = request(server.address(), super::FILE, &guess).unwrap();
+		guess[0] ^= 1;
+		let (_, wrong) = request(server.address(), super::FILE, &guess).unwrap();
+		assert!(right >= Duration::from_millis(20));
+		assert!(wrong < right);
+	}
+
+	#[test]
+	fn recover_mac_prefix() {
+		// the whole MAC would take minutes, so just the first two bytes
+		let server = HmacServer::start(b"key".to_vec(), Duration::from_millis(10)).unwrap();
+		let mac = cryptopalslib::mac::hmac::<Sha1>(b"key", super::FILE.as_bytes());
+		let output = super::recover_mac(server.address(), super::FILE, 2, 3);
+		assert_eq!(output, &mac[..2]);
+	}
+}
@@ -0,0 +1,71 @@
+// Break HMAC-SHA1 with a slightly less artificial timing leak
+// Reduce the sleep in your "insecure_compare" until your previous solution
+// breaks. (Try 5ms to start.)
+
+// Now break it again.
+
+extern crate openssl;
+extern crate cryptopalslib;
+
+#[cfg(not(test))]
+use cryptopalslib::hmac_server::{request, HmacServer};
+#[cfg(not(test))]
+use std::net::SocketAddr;
+#[cfg(not(test))]
+use std::time::Duration;
+
+static FILE: &'static str = "foo";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 4, Challenge 32");
+
+	let key = openssl::crypto::rand::rand_bytes(16);
+	let server = HmacServer::start(key, Duration::from_millis(5)).unwrap();
+	// with a smaller leak, each guess is timed several times so that noise
+	// and the odd slow request can be averaged out
+	let mac = recover_mac(server.address(), FILE, 5);
+	println!("mac: {:?}", cryptopalslib::convert::decimals_to_hex_string(mac.clone()));
+	println!("status: {:?}", request(server.address(), FILE, &mac).unwrap().0);
+}
+
+/// Recovers the HMAC-SHA1 of `file` by timing requests to the server.
+#[cfg(not(test))]
+fn recover_mac(address: SocketAddr, file: &str, samples: usize) -> Vec<u8> {
+	cryptopalslib::attacks::timing_leak::recover_mac(20, samples, |guess| {
+		let (status, elapsed) = request(address, file, guess).unwrap();
+		(status == 200, elapsed.as_secs() * 1_000_000_000 + elapsed.subsec_nanos() as u64)
+	})
+}
+
+#[cfg(test)]
+mod set4challenge32 {
+	use cryptopalslib::clock::Clock;
+	use cryptopalslib::hash::sha1::Sha1;
+	use cryptopalslib::hmac_server::{request, HmacServer};
+	use cryptopalslib::timing::{NoiseModel, SimulatedTimer};
+	use std::time::Duration;
+
+	#[test]
+	fn first_byte_leaks() {
+		let server = HmacServer::start(b"key".to_vec(), Duration::from_millis(5)).unwrap();
+		let mut guess = vec!(0; 20);
+		guess[0] = cryptopalslib::mac::hmac::<Sha1>(b"key", super::FILE.as_bytes())[0];
+		let (_, right) = request(server.address(), super::FILE, &guess).unwrap();
+		assert!(right >= Duration::from_millis(5));
+	}
+
+	#[test]
+	fn recover_mac_simulated() {
+		// a 5ms leak, with a millisecond of jitter and the odd 50ms stall
+		let noise = NoiseModel { base: 2000000, jitter: 1000000.0, spike_probability: 0.02, spike: 50000000 };
+		let timer = SimulatedTimer::new(noise, 32);
+		let mac = cryptopalslib::mac::hmac::<Sha1>(b"key", super::FILE.as_bytes());
+		// with stalls this common, a few guesses get two or three of them, so
+		// take more samples and the median to see past them
+		let output = cryptopalslib::attacks::timing_leak::recover_mac_with(20, 7, cryptopalslib::attacks::timing_leak::median, |guess| {
+			timer.measure(|clock| cryptopalslib::mac::insecure_compare_with(&mac, guess, || clock.sleep_nanos(5000000)))
+		});
+		assert_eq!(output, mac);
+	}
+}
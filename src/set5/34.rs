@@ -0,0 +1,96 @@
+// Implement a MITM key-fixing attack on Diffie-Hellman with parameter injection
+// Use the code you just worked out to build a protocol and an "echo" bot.
+// You don't actually have to do the network part of this if you don't want;
+// just simulate that. The protocol is:
+
+// A->B
+//     Send "p", "g", "A"
+// B->A
+//     Send "B"
+// A->B
+//     Send AES-CBC(SHA1(s)[0:16], iv=random(16), msg) + iv
+// B->A
+//     Send AES-CBC(SHA1(s)[0:16], iv=random(16), A's msg) + iv
+
+// (In other words, derive an AES key from DH with SHA1, use it in both
+// directions, and do CBC with random IVs appended or prepended to the
+// message).
+
+// Now implement the following MITM attack:
+
+// A->M
+//     Send "p", "g", "A"
+// M->B
+//     Send "p", "g", "p"
+// B->M
+//     Send "B"
+// M->A
+//     Send "p"
+// A->M
+//     Send AES-CBC(SHA1(s)[0:16], iv=random(16), msg) + iv
+// M->B
+//     Relay that to B
+// B->M
+//     Send AES-CBC(SHA1(s)[0:16], iv=random(16), A's msg) + iv
+// M->A
+//     Relay that to A
+
+// M should be able to decrypt the messages. "A" and "B" in the protocol ---
+// the public keys, over the wire --- have been swapped out with "p". Do the
+// DH math on this quickly to see what that does to the predictability of
+// the key.
+
+// Decrypt the messages from M's vantage point as they go by.
+
+// Note that you don't actually have to inject bogus parameters to make this
+// attack work; you could just generate Ma, MA, Mb, and MB as valid DH
+// parameters to do a generic MITM attack. But do the parameter injection
+// attack; it's going to come up again.
+
+extern crate cryptopalslib;
+
+#[cfg(not(test))]
+use cryptopalslib::attacks::dh_mitm::ParameterInjection;
+#[cfg(not(test))]
+use cryptopalslib::dh::Group;
+#[cfg(not(test))]
+use cryptopalslib::protocol::Handshake;
+
+static MESSAGE: &'static [u8] = b"Don't let me down";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 5, Challenge 34");
+
+	let mut mallory = ParameterInjection::new();
+	let (alice, bob) = cryptopalslib::protocol::run(&Group::modp_1536(), Handshake::Direct, MESSAGE, &mut mallory);
+	println!("Bob received: {:?}", alice_or_bob(bob));
+	println!("Alice got back: {:?}", alice_or_bob(alice));
+	for plaintext in mallory.recovered {
+		println!("Mallory read: {:?}", String::from_utf8_lossy(&plaintext));
+	}
+}
+
+#[cfg(not(test))]
+fn alice_or_bob(result: Result<Vec<u8>, cryptopalslib::error::Error>) -> String {
+	match result {
+		Ok(plaintext) => String::from_utf8_lossy(&plaintext).into_owned(),
+		Err(e) => format!("error: {}", e),
+	}
+}
+
+#[cfg(test)]
+mod set5challenge34 {
+	use cryptopalslib::attacks::dh_mitm::ParameterInjection;
+	use cryptopalslib::dh::Group;
+	use cryptopalslib::protocol::Handshake;
+
+	#[test]
+	fn mallory_reads_both_messages() {
+		let mut mallory = ParameterInjection::new();
+		let (alice, bob) = cryptopalslib::protocol::run(&Group::modp_1536(), Handshake::Direct, super::MESSAGE, &mut mallory);
+		assert_eq!(alice.unwrap(), super::MESSAGE);
+		assert_eq!(bob.unwrap(), super::MESSAGE);
+		assert_eq!(mallory.recovered, vec!(super::MESSAGE.to_vec(), super::MESSAGE.to_vec()));
+	}
+}
@@ -0,0 +1,71 @@
+// Implement DH with negotiated groups, and break with malicious "g"
+// parameters
+
+// A->B
+//     Send "p", "g"
+// B->A
+//     Send ACK
+// A->B
+//     Send "A"
+// B->A
+//     Send "B"
+// A->B
+//     Send AES-CBC(SHA1(s)[0:16], iv=random(16), msg) + iv
+// B->A
+//     Send AES-CBC(SHA1(s)[0:16], iv=random(16), A's msg) + iv
+
+// Do the MITM attack again, but play with "g". What happens with:
+
+//     g = 1
+//     g = p
+//     g = p - 1
+
+// Write attacks for each.
+
+// When does this ever happen?
+// Honestly, not that often in real-world systems. If you can mess with "g",
+// chances are you can mess with something worse. Most systems pre-agree on a
+// static DH group. But the same construction exists in Elliptic Curve
+// Diffie-Hellman, and this becomes more relevant there.
+
+extern crate cryptopalslib;
+
+use cryptopalslib::attacks::dh_mitm::{GeneratorInjection, InjectedGenerator};
+use cryptopalslib::dh::Group;
+use cryptopalslib::protocol::Handshake;
+
+static MESSAGE: &'static [u8] = b"Negotiate this";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 5, Challenge 35");
+
+	for &generator in [InjectedGenerator::One, InjectedGenerator::P, InjectedGenerator::PMinusOne].iter() {
+		let (recovered, echoed) = attack(generator);
+		println!("g = {:?}: Mallory read {:?}, Alice's echo {}", generator,
+			recovered.iter().map(|x| String::from_utf8_lossy(x).into_owned()).collect::<Vec<_>>(),
+			if echoed { "worked" } else { "failed" });
+	}
+}
+
+/// Runs the negotiated protocol with Mallory injecting `generator`.
+/// Returns what Mallory read, and whether Alice got her message back.
+fn attack(generator: InjectedGenerator) -> (Vec<Vec<u8>>, bool) {
+	let mut mallory = GeneratorInjection::new(generator);
+	let (alice, _) = cryptopalslib::protocol::run(&Group::modp_1536(), Handshake::Negotiated, MESSAGE, &mut mallory);
+	(mallory.recovered, alice.ok() == Some(MESSAGE.to_vec()))
+}
+
+#[cfg(test)]
+mod set5challenge35 {
+	use cryptopalslib::attacks::dh_mitm::InjectedGenerator;
+
+	#[test]
+	fn every_generator() {
+		for &generator in [InjectedGenerator::One, InjectedGenerator::P, InjectedGenerator::PMinusOne].iter() {
+			let (recovered, echoed) = super::attack(generator);
+			assert_eq!(recovered, vec!(super::MESSAGE.to_vec(), super::MESSAGE.to_vec()));
+			assert!(echoed);
+		}
+	}
+}
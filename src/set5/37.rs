@@ -0,0 +1,53 @@
+// Break SRP with a zero key
+// Get your SRP working in an actual client-server setting. "Log in" with a
+// valid password using the protocol.
+
+// Now log in without your password by having the client send 0 as its "A"
+// value. What does this to the "S" value that both sides compute?
+
+// Now log in without your password by having the client send N, N*2, &c.
+
+// Cryptanalytic MVP award
+// Trevor Perrin and Nate Lawson taught us this attack 7 years ago. It is
+// excellent. Attacks on DH are tricky to "operationalize". But this attack
+// uses the same concepts, and results in auth bypass. Almost every
+// implementation of SRP we've ever seen has this flaw; if you see a new
+// one, go look for this bug.
+
+extern crate cryptopalslib;
+
+use cryptopalslib::srp::{Parameters, Server};
+
+static EMAIL: &'static str = "alice@example.com";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 5, Challenge 37");
+
+	let (parameters, mut server) = setup();
+	for multiple in 0..3 {
+		let success = cryptopalslib::attacks::srp::zero_key_login(&mut server, &parameters, EMAIL, multiple);
+		println!("A = {} * N: logged in: {}", multiple, success);
+	}
+}
+
+/// Creates a server with one user, whose password we never learn.
+fn setup() -> (Parameters, Server) {
+	let parameters = Parameters::new();
+	let mut server = Server::new(parameters.clone());
+	let password = cryptopalslib::convert::decimals_to_hex_string(cryptopalslib::bignum::BigUint::random_below(&parameters.n).to_bytes_be());
+	server.register(EMAIL, &password);
+	(parameters, server)
+}
+
+#[cfg(test)]
+mod set5challenge37 {
+
+	#[test]
+	fn login_without_password() {
+		let (parameters, mut server) = super::setup();
+		for multiple in 0..3 {
+			assert!(cryptopalslib::attacks::srp::zero_key_login(&mut server, &parameters, super::EMAIL, multiple));
+		}
+	}
+}
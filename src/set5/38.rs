@@ -0,0 +1,120 @@
+// Offline dictionary attack on simplified SRP
+// S
+//     x = SHA256(salt|password)
+//     v = g**x % n
+// C->S
+//     I, A = g**a % n
+// S->C
+//     salt, B = g**b % n, u = 128 bit random number
+// C
+//     x = SHA256(salt|password)
+//     S = B**(a + ux) % n
+//     K = SHA256(S)
+// S
+//     S = (A * v ** u)**b % n
+//     K = SHA256(S)
+// C->S
+//     Send HMAC-SHA256(K, salt)
+// S->C
+//     Send "OK" if HMAC-SHA256(K, salt) validates
+
+// Note that in this protocol, the server's "B" parameter doesn't depend on
+// the password (it's just a Diffie Hellman public key).
+
+// Make sure the protocol works given a valid password.
+
+// Now, run the protocol as a MITM attacker: pose as the server and use
+// arbitrary values for b, B, u, and salt.
+
+// Crack the password from A's HMAC-SHA256(K, salt).
+
+extern crate openssl;
+extern crate cryptopalslib;
+
+#[cfg(not(test))]
+use std::env;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::prelude::*;
+use std::path::Path;
+use cryptopalslib::attacks::srp::DictionaryAttacker;
+use cryptopalslib::srp::Parameters;
+
+static EMAIL: &'static str = "bob@example.com";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 5, Challenge 38");
+
+	if env::args().count() < 2 {
+		panic!("Must pass a wordlist")
+	}
+
+	let arg = match env::args().nth(1) {
+		Some(s) => s,
+		None => panic!("No input argument given")
+	};
+
+	let words = read_words(Path::new(&arg));
+	match crack_random_password(&words) {
+		Some((_, Some(password))) => println!("password: {:?}", password),
+		Some((_, None)) => println!("Couldn't crack the password"),
+		None => println!("The wordlist is empty"),
+	}
+}
+
+/// Reads a wordlist, one word per line.
+fn read_words(path: &Path) -> Vec<String> {
+	let file = BufReader::new(File::open(path).unwrap());
+	file.lines()
+		.map(|x| x.unwrap().trim().to_string())
+		.filter(|x| !x.is_empty())
+		.collect()
+}
+
+/// Has the client log in with a random word from the list, with Mallory
+/// posing as the server, then cracks the password from what Mallory saw.
+/// Returns the word the client used and the cracked password, or `None`
+/// if the list is empty.
+fn crack_random_password(words: &[String]) -> Option<(String, Option<String>)> {
+	if words.is_empty() {
+		return None;
+	}
+
+	let parameters = Parameters::new();
+	let index = openssl::crypto::rand::rand_bytes(4).iter().fold(0, |x, &y| (x << 8) | y as usize) % words.len();
+
+	let mut mallory = DictionaryAttacker::new(parameters.clone());
+	cryptopalslib::srp::simple_login(&mut mallory, &parameters, EMAIL, &words[index]);
+	let cracked = mallory.crack(words.iter().map(|x| x.as_str())).map(|x| x.to_string());
+	Some((words[index].clone(), cracked))
+}
+
+#[cfg(test)]
+mod set5challenge38 {
+	use cryptopalslib::srp::{HonestSimpleServer, Parameters};
+	use std::path::Path;
+
+	fn words() -> Vec<String> {
+		super::read_words(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/set5/38.txt")))
+	}
+
+	#[test]
+	fn honest_login() {
+		let parameters = Parameters::new();
+		let mut server = HonestSimpleServer::new(parameters.clone());
+		server.register(super::EMAIL, "sunshine");
+		assert!(cryptopalslib::srp::simple_login(&mut server, &parameters, super::EMAIL, "sunshine"));
+	}
+
+	#[test]
+	fn crack_password() {
+		let (password, cracked) = super::crack_random_password(&words()).unwrap();
+		assert_eq!(cracked, Some(password));
+	}
+
+	#[test]
+	fn empty_wordlist() {
+		assert_eq!(super::crack_random_password(&[]), None);
+	}
+}
@@ -0,0 +1,77 @@
+// Implement RSA
+// There are two annoying things about implementing RSA. Both of them involve
+// key generation; the actual encryption/decryption in RSA is trivial.
+
+// First, you need to generate random primes. You can't just agree on a prime
+// ahead of time, like you do in DH. You can write this algorithm yourself,
+// but I just cheat and use OpenSSL's BN library to do the work.
+
+// The second is that you need an "invmod" operation (the multiplicative
+// inverse), which is not an operation that is wired into your language's
+// number library. The algorithm is just a couple lines, but I always lose an
+// hour getting it to work.
+
+// I recommend you not bother with primegen, but do take the time to get your
+// own EGCD and invmod algorithm working.
+
+// Now:
+
+// Generate 2 random primes. We'll use small numbers to start, so you can just
+// pick them out of a prime table. Potential area for bugs here: it's easy to
+// accidentally pick 2 primes whose product is divisible by 3.
+// Let n be p * q. Your RSA math is modulo n.
+// Let et be (p-1)*(q-1) (the "totient"). You need this value only for keygen.
+// Let e be 3.
+// Compute d = invmod(e, et). invmod(17, 3120) is 2753.
+// Your public key is [e, n]. Your private key is [d, n].
+// To encrypt: c = m**e%n. To decrypt: m = c**d%n
+// Test this out with a number, like "42".
+// Repeat with bignum primes (keep e=3).
+// Finally, to encrypt a string, do something cheesy, like convert the string
+// to hex and put "0x" on the front of it to turn it into a number. The math
+// cares not how stupidly you feed it strings.
+
+extern crate cryptopalslib;
+
+use cryptopalslib::bignum::BigUint;
+use cryptopalslib::rsa::PrivateKey;
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 5, Challenge 39");
+
+	println!("invmod(17, 3120) = {}", BigUint::from(17).mod_inverse(&BigUint::from(3120)).unwrap());
+
+	let key = PrivateKey::generate(1024, &BigUint::from(3));
+	let m = BigUint::from(42);
+	println!("42 -> {}", roundtrip(&key, &m));
+
+	let text = b"Cooking MC's like a pound of bacon";
+	let output = roundtrip(&key, &BigUint::from_bytes_be(text));
+	println!("{:?}", String::from_utf8_lossy(&output.to_bytes_be()));
+}
+
+/// Encrypts and then decrypts a number.
+fn roundtrip(key: &PrivateKey, m: &BigUint) -> BigUint {
+	let c = key.public_key().encrypt_raw(m);
+	key.decrypt_raw(&c)
+}
+
+#[cfg(test)]
+mod set5challenge39 {
+	use cryptopalslib::bignum::BigUint;
+	use cryptopalslib::rsa::PrivateKey;
+
+	#[test]
+	fn invmod() {
+		assert_eq!(BigUint::from(17).mod_inverse(&BigUint::from(3120)), Some(BigUint::from(2753)));
+	}
+
+	#[test]
+	fn roundtrip() {
+		let key = PrivateKey::generate(1024, &BigUint::from(3));
+		let m = BigUint::from_bytes_be(b"Cooking MC's like a pound of bacon");
+		assert_eq!(super::roundtrip(&key, &BigUint::from(42)), BigUint::from(42));
+		assert_eq!(super::roundtrip(&key, &m), m);
+	}
+}
@@ -0,0 +1,80 @@
+// Implement unpadded message recovery oracle
+// Nate Lawson says we should stop calling it "RSA padding" and start calling
+// it "RSA armoring". Here's why.
+
+// Imagine a web application, again with the Javascript encryption, taking
+// RSA-encrypted messages which (again: Javascript) aren't padded before
+// encryption at all.
+
+// You can submit an arbitrary RSA blob and the server will return plaintext.
+// But you can't submit the same message twice: let's say the server keeps
+// hashes of previous messages for some liveness interval, and that the
+// message has an embedded timestamp:
+
+// {
+//   time: 1356304276,
+//   social: '555-55-5555',
+// }
+
+// You'd like to capture other people's messages and use the server to
+// decrypt them. But when you try, the server takes the hash of the
+// ciphertext and uses it to reject the request. Any bit you flip in the
+// ciphertext irrevocably scrambles the decryption.
+
+// This turns out to be trivially breakable:
+
+// Capture the ciphertext C
+// Let N and E be the public modulus and exponent respectively
+// Let S be a random number > 1 mod N. Doesn't matter what.
+// Now:
+// C' = ((S**E mod N) C) mod N
+// Submit C', which appears totally different from C, to the server,
+// recovering P', which appears totally different from P
+// Now:
+//           P'
+//     P = -----  mod N
+//           S
+// Oops!
+
+// Implement that attack.
+
+// Careful about division in cyclic groups.
+// Remember: you don't simply divide mod N; you multiply by the multiplicative
+// inverse mod N. So you'll need a modinv() function.
+
+extern crate cryptopalslib;
+
+use cryptopalslib::attacks::rsa_unpadded::DecryptionServer;
+use cryptopalslib::bignum::BigUint;
+use cryptopalslib::rsa::PrivateKey;
+
+static MESSAGE: &'static [u8] = b"{time: 1356304276, social: '555-55-5555'}";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 6, Challenge 41");
+
+	let output = capture_and_recover();
+	println!("recovered: {:?}", output.map(|x| String::from_utf8_lossy(&x.to_bytes_be()).into_owned()));
+}
+
+/// Has a victim submit the message to the server, then recovers it from the
+/// captured ciphertext.
+fn capture_and_recover() -> Option<BigUint> {
+	let mut server = DecryptionServer::new(PrivateKey::generate(1024, &BigUint::from(65537)));
+	let public = server.public_key();
+	let c = public.encrypt_raw(&BigUint::from_bytes_be(MESSAGE));
+	server.decrypt(&c);
+
+	cryptopalslib::attacks::rsa_unpadded::recover(&public, &c, |x| server.decrypt(x))
+}
+
+#[cfg(test)]
+mod set6challenge41 {
+
+	#[test]
+	fn recover_message() {
+		let output = super::capture_and_recover().unwrap();
+		assert_eq!(output.to_bytes_be(), super::MESSAGE);
+	}
+}
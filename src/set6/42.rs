@@ -0,0 +1,102 @@
+// Bleichenbacher's e=3 RSA Attack
+// Crypto-tourism informational placard.
+// This attack broke Firefox's TLS certificate validation several years ago.
+// You could write a Python script to fake an RSA signature for any
+// certificate. We find new instances of it every other year or so.
+
+// RSA with an encrypting exponent of 3 is popular, because it makes the RSA
+// math faster.
+
+// With e=3 RSA, encryption is just cubing a number mod the public encryption
+// modulus:
+
+//  c = m ** 3 % n
+
+// e=3 is secure as long as we can make assumptions about the message blocks
+// we're encrypting. The worry with low-exponent RSA is that the message
+// blocks we process won't be large enough to wrap the modulus after being
+// cubed. The block 00:02 (imagine sufficient zero-padding) can be
+// "encrypted" in e=3 RSA; it is simply 00:08.
+
+// When RSA is used to sign, rather than encrypt, the operations are
+// reversed; the verifier "decrypts" the message by cubing it. This produces
+// a "plaintext" which the verifier checks for validity.
+
+// When you use RSA to sign a message, you supply it a block input that
+// contains a message digest. The PKCS1.5 standard formats that block as:
+
+// 00h 01h ffh ffh ... ffh ffh 00h ASN.1 GOOP HASH
+
+// As intended, the ffh bytes in that block expand to fill the whole block,
+// producing a "right-justified" hash (the last byte of the hash is the last
+// byte of the message).
+
+// There was, 7 years ago, a common implementation flaw with RSA verifiers:
+// they'd verify signatures by "decrypting" them (cubing them modulo the
+// public exponent) and then "parsing" them by looking for 00h 01h ... ffh
+// 00h ASN.1 HASH.
+
+// This is a bug because it implies the verifier isn't checking all the
+// padding. If you don't check the padding, you leave open the possibility
+// that instead of hundreds of ffh bytes, you have only a few, which if you
+// think about it means there could be squizzilions of possible numbers that
+// could produce a valid-looking signature.
+
+// How to find such a block? Find a number that when cubed (a) doesn't wrap
+// the modulus (thus bypassing the key entirely) and (b) produces a block
+// that starts "00h 01h ffh ... 00h ASN.1 HASH".
+
+// There are two ways to approach this problem:
+
+// You can work from Hal Finney's writeup, available on Google, of how
+// Bleichenbacher explained the math "so that you can do it by hand with a
+// pencil".
+// You can implement an integer cube root in your language, format the
+// message block you want to forge, leaving sufficient trailing zeros at the
+// end to fill with garbage, then take the cube-root of that block.
+// Forge a 1024-bit RSA signature for the string "hi mom". Make sure your
+// implementation actually accepts the signature!
+
+extern crate cryptopalslib;
+
+#[cfg(not(test))]
+use cryptopalslib::attacks::rsa_signature_forgery::{forge, sloppy_verify};
+#[cfg(not(test))]
+use cryptopalslib::bignum::BigUint;
+#[cfg(not(test))]
+use cryptopalslib::rsa::PrivateKey;
+use cryptopalslib::rsa::{HashAlgorithm, PublicKey};
+
+static MESSAGE: &'static [u8] = b"hi mom";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 6, Challenge 42");
+
+	let public = PrivateKey::generate(1024, &BigUint::from(3)).public_key();
+	let signature = forge(&public, HashAlgorithm::Sha1, MESSAGE).unwrap();
+	println!("forged: {}", cryptopalslib::convert::decimals_to_hex_string(signature.clone()));
+	println!("sloppy verifier accepts: {}", sloppy_verify(&public, MESSAGE, &signature));
+	println!("correct verifier accepts: {}", correct_verify(&public, &signature));
+}
+
+/// Checks a signature on the message with the library's verifier, which
+/// compares the whole encoded block.
+fn correct_verify(public: &PublicKey, signature: &[u8]) -> bool {
+	public.verify(HashAlgorithm::Sha1, MESSAGE, signature)
+}
+
+#[cfg(test)]
+mod set6challenge42 {
+	use cryptopalslib::attacks::rsa_signature_forgery::{forge, sloppy_verify};
+	use cryptopalslib::bignum::BigUint;
+	use cryptopalslib::rsa::{HashAlgorithm, PrivateKey};
+
+	#[test]
+	fn forge_hi_mom() {
+		let public = PrivateKey::generate(1024, &BigUint::from(3)).public_key();
+		let signature = forge(&public, HashAlgorithm::Sha1, super::MESSAGE).unwrap();
+		assert!(sloppy_verify(&public, super::MESSAGE, &signature));
+		assert!(!super::correct_verify(&public, &signature));
+	}
+}
@@ -0,0 +1,145 @@
+// DSA key recovery from nonce
+// Step 1: Relocate so that you are out of easy travel distance of us.
+
+// Step 2: Implement DSA, up to signing and verifying, including parameter
+// generation.
+
+// Hah-hah you're too far away to come punch us.
+
+// Just kidding you can skip the parameter generation part if you want; if
+// you do, use these params:
+
+//  p = 800000000000000089e1855218a0e7dac38136ffafa72eda7
+//      859f2171e25e65eac698c1702578b07dc2a1076da241c76c6
+//      2d374d8389ea5aeffd3226a0530cc565f3bf6b50929139ebe
+//      ac04f48c3c84afb796d61e5a4f9a8fda812ab59494232c7d2
+//      b4deb50aa18ee9e132bfa85ac4374d7f9091abc3d015efc87
+//      1a584471bb1
+
+//  q = f4f47f05794b256174bba6e9b396a7707e563c5b
+
+//  g = 5958c9d3898b224b12672c0b98e06c60df923cb8bc999d119
+//      458fef538b8fa4046c8db53039db620c094c9fa077ef389b5
+//      322a559946a71903f990f1f7e0e025e2d7f7cf494aff1a047
+//      0f5b64c36b625a097f1651fe775323556fe00b3608c887892
+//      878480e99041be601a62166ca6894bdd41a7054ec89f756ba
+//      9fc95302291
+
+// ("But I want smaller params!" Then generate them yourself.)
+
+// The DSA signing operation generates a random subkey "k". You know this
+// because you implemented the DSA sign operation.
+
+// This is the first and easier of two challenges regarding the DSA "k"
+// subkey.
+
+// Given a known "k", it's trivial to recover the DSA private key "x":
+
+//           (s * k) - H(msg)
+//       x = ----------------  mod q
+//                   r
+
+// Do this a couple times to prove to yourself that you grok it. Capture it
+// in a function of some sort.
+
+// Now then. I used the parameters above. I generated a keypair. My pubkey
+// is:
+
+//   y = 84ad4719d044495496a3201c8ff484feb45b962e7302e56a392aee4
+//       abab3e4bdebf2955b4736012f21a08084056b19bcd7fee56048e004
+//       e44984e2f411788efdc837a0d2e5abb7b555039fd243ac01f0fb2ed
+//       1dec568280ce678e931868d23eb095fde9d3779191b8c0299d6e07b
+//       bb283e6633451e535c45513b2d33c99ea17
+
+// I signed
+
+// For those that envy a MC it can be hazardous to your health
+// So be friendly, a matter of life and death, just like a etch-a-sketch
+
+// (My SHA1 for this string was d2d0714f014a9784047eaeccf956520045c45265; I
+// don't know what NIST wants you to do, but when I convert that hash to an
+// integer I get: 0xd2d0714f014a9784047eaeccf956520045c45265).
+
+// I get:
+
+//   r = 548099063082341131477253921760299949438196259240
+//   s = 857042759984254168557880549501802188789837994940
+
+// I signed this string with a broken implemention of DSA that generated "k"
+// values between 0 and 2^16. What's my private key?
+
+// Its SHA-1 fingerprint (after being converted to hex) is:
+
+// 0954edd5e0afe5542a4adf012611a91912a3ec16
+
+// Obviously, it also generates the same signature for that string.
+
+extern crate cryptopalslib;
+
+use cryptopalslib::bignum::BigUint;
+use cryptopalslib::dsa::{Parameters, PrivateKey, PublicKey, Signature};
+use cryptopalslib::hash::MerkleDamgard;
+use cryptopalslib::hash::sha1::Sha1;
+use cryptopalslib::rsa::HashAlgorithm;
+
+static Y: &'static str = "\
+	84ad4719d044495496a3201c8ff484feb45b962e7302e56a392aee4abab3e4bdebf2955b4736012f21a08084056b19bcd7fee5\
+	6048e004e44984e2f411788efdc837a0d2e5abb7b555039fd243ac01f0fb2ed1dec568280ce678e931868d23eb095fde9d3779\
+	191b8c0299d6e07bbb283e6633451e535c45513b2d33c99ea17";
+
+static MESSAGE: &'static [u8] = b"For those that envy a MC it can be hazardous to your health\n\
+	So be friendly, a matter of life and death, just like a etch-a-sketch\n";
+
+static R: &'static str = "548099063082341131477253921760299949438196259240";
+static S: &'static str = "857042759984254168557880549501802188789837994940";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 6, Challenge 43");
+
+	match recover_key() {
+		Some(key) => println!("x = {:x}, fingerprint {}", key.x, fingerprint(&key)),
+		None => println!("no k below 2^16 matches"),
+	}
+}
+
+/// Searches every k below 2^16 for the one behind the challenge's signature.
+fn recover_key() -> Option<PrivateKey> {
+	let public = PublicKey { params: Parameters::challenge(), y: BigUint::from_hex(Y) };
+	let signature = Signature { r: BigUint::from_decimal(R), s: BigUint::from_decimal(S) };
+	cryptopalslib::attacks::dsa_nonce::recover_from_small_nonce(&public, HashAlgorithm::Sha1, MESSAGE, &signature, 0..1 << 16)
+}
+
+/// The SHA-1 of x written out in hex, which the challenge gives to check the
+/// answer against.
+fn fingerprint(key: &PrivateKey) -> String {
+	cryptopalslib::convert::decimals_to_hex_string(Sha1::digest(key.x.to_hex().as_bytes()))
+}
+
+#[cfg(test)]
+mod set6challenge43 {
+	use cryptopalslib::bignum::BigUint;
+	use cryptopalslib::dsa::{Parameters, PrivateKey};
+	use cryptopalslib::rsa::HashAlgorithm;
+
+	#[test]
+	fn message_hash() {
+		let hash = cryptopalslib::convert::decimals_to_hex_string(HashAlgorithm::Sha1.hash(super::MESSAGE));
+		assert_eq!(hash, "d2d0714f014a9784047eaeccf956520045c45265");
+	}
+
+	#[test]
+	fn known_nonce() {
+		let key = PrivateKey::generate(&Parameters::challenge());
+		let k = BigUint::from(0x1234567);
+		let signature = key.sign_with_nonce(HashAlgorithm::Sha1, b"hello", &k).unwrap();
+		let m = cryptopalslib::dsa::digest_to_integer(&HashAlgorithm::Sha1.hash(b"hello"), &key.params.q);
+		assert_eq!(cryptopalslib::attacks::dsa_nonce::x_from_nonce(&key.params, &m, &signature, &k), Some(key.x));
+	}
+
+	#[test]
+	fn recover_key() {
+		let key = super::recover_key().unwrap();
+		assert_eq!(super::fingerprint(&key), "0954edd5e0afe5542a4adf012611a91912a3ec16");
+	}
+}
@@ -0,0 +1,123 @@
+// DSA nonce recovery from repeated nonce
+// Cryptanalytic MVP award.
+// This attack (in an elliptic curve group) broke the PS3. It is a great,
+// great attack.
+
+// In this file find a collection of DSA-signed messages. (NB: each msg has a
+// trailing space.)
+
+// These were signed under the following pubkey:
+
+// y = 2d026f4bf30195ede3a088da85e398ef869611d0f68f0713d51c9c1a
+//     3a26c95105d915e2d8cdf26d056b86b8a7b85519b1c23cc3ecdc6062
+//     650462e3063bd179c2a6581519f674a61f1d89a1fff27171ebc1b93d
+//     4dc57bceb7ae2430f98a6a4d83d8279ee65d71c1203d2c96d65ebbf7
+//     cce9d32971c3de5084cce04a2e147821
+
+// (using the same domain parameters as the previous exercise)
+
+// It should not be hard to find the messages for which we have accidentally
+// used a repeated "k". Given a pair of such messages, you can discover the
+// "k" we used with the following formula:
+
+//          (m1 - m2)
+//      k = --------- mod q
+//          (s1 - s2)
+
+// 9th Grade Math: Study It!
+// If you want to demystify this, work out that equation from the original
+// DSA equations.
+
+// Basic cyclic group math operations want to screw you
+// Remember all this math is mod q; s2 may be larger than s1, for instance,
+// which isn't a problem if you're doing the subtraction mod q. If you're
+// like me, you'll definitely lose an hour to forgetting a paren or a mod q.
+// (And then lose another hour to trying to find the paren error since you're
+// just guessing.)
+
+// What's my private key? Its SHA-1 (from hex) is:
+
+//    ca8f6f7c66fa362d40760d135b763eb8527d3d52
+
+extern crate cryptopalslib;
+
+#[cfg(not(test))]
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+use cryptopalslib::attacks::dsa_nonce::SignedMessage;
+use cryptopalslib::bignum::BigUint;
+use cryptopalslib::dsa::{Parameters, PrivateKey, PublicKey};
+use cryptopalslib::hash::MerkleDamgard;
+use cryptopalslib::hash::sha1::Sha1;
+
+static Y: &'static str = "\
+	2d026f4bf30195ede3a088da85e398ef869611d0f68f0713d51c9c1a3a26c95105d915e2d8cdf26d056b86b8a7b85519\
+	b1c23cc3ecdc6062650462e3063bd179c2a6581519f674a61f1d89a1fff27171ebc1b93d4dc57bceb7ae2430f98a6a4d\
+	83d8279ee65d71c1203d2c96d65ebbf7cce9d32971c3de5084cce04a2e147821";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 6, Challenge 44");
+
+	if env::args().count() < 2 {
+		panic!("Must pass a file of signed messages")
+	}
+
+	let arg = match env::args().nth(1) {
+		Some(s) => s,
+		None => panic!("No input argument given")
+	};
+
+	match recover_key(&read_messages(Path::new(&arg))) {
+		Some(key) => println!("x = {:x}, fingerprint {}", key.x, fingerprint(&key)),
+		None => println!("no messages share a nonce"),
+	}
+}
+
+/// Reads the signed messages from a file.
+fn read_messages(path: &Path) -> Vec<SignedMessage> {
+	let mut text = String::new();
+	File::open(path).unwrap().read_to_string(&mut text).unwrap();
+	cryptopalslib::attacks::dsa_nonce::parse_signed_messages(&text).unwrap()
+}
+
+/// Finds a pair of messages signed with the same k, and recovers the key.
+fn recover_key(messages: &[SignedMessage]) -> Option<PrivateKey> {
+	let public = PublicKey { params: Parameters::challenge(), y: BigUint::from_hex(Y) };
+	cryptopalslib::attacks::dsa_nonce::recover_from_reused_nonce(&public, messages)
+}
+
+/// The SHA-1 of x written out in hex.
+fn fingerprint(key: &PrivateKey) -> String {
+	cryptopalslib::convert::decimals_to_hex_string(Sha1::digest(key.x.to_hex().as_bytes()))
+}
+
+#[cfg(test)]
+mod set6challenge44 {
+	use cryptopalslib::dsa::digest_to_integer;
+	use cryptopalslib::rsa::HashAlgorithm;
+	use std::path::Path;
+
+	fn messages() -> Vec<super::SignedMessage> {
+		super::read_messages(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/set6/44.txt")))
+	}
+
+	#[test]
+	fn read_file() {
+		let messages = messages();
+		assert_eq!(messages.len(), 8);
+		for message in &messages {
+			assert!(message.message.ends_with(' '));
+			let m = digest_to_integer(&HashAlgorithm::Sha1.hash(message.message.as_bytes()), &super::Parameters::challenge().q);
+			assert_eq!(message.m, m);
+		}
+	}
+
+	#[test]
+	fn recover_key() {
+		let key = super::recover_key(&messages()).unwrap();
+		assert_eq!(super::fingerprint(&key), "ca8f6f7c66fa362d40760d135b763eb8527d3d52");
+	}
+}
@@ -0,0 +1,67 @@
+// DSA parameter tampering
+// Take your DSA code from the previous exercise. Imagine it as part of an
+// algorithm in which the client was allowed to propose domain parameters
+// (the p and q moduli, and the g generator).
+
+// This would be bad, because attackers could trick victims into accepting
+// bad parameters. Vaudenay gave two examples of bad generator parameters:
+// generators that were 0 mod p, and generators that were 1 mod p.
+
+// Use the parameters from the previous exercise, but substitute 0 for "g".
+// Generate a signature. You will notice something bad. Verify the
+// signature. Now verify any other signature, for any other string.
+
+// Now, try (p+1) as "g". With this "g", you can generate a magic signature
+// s, r for any DSA public key that will validate against any string. For
+// arbitrary z:
+
+//   r = ((y**z) % p) % q
+
+//         r
+//   s =  --- % q
+//         z
+
+// Sign "Hello, world". And "Goodbye, world".
+
+extern crate cryptopalslib;
+
+use cryptopalslib::attacks::dsa_generator::{magic_signature, trusting_verify, TamperedGenerator};
+use cryptopalslib::dsa::{Parameters, PrivateKey};
+use cryptopalslib::rsa::HashAlgorithm;
+
+static MESSAGES: [&'static [u8]; 2] = [b"Hello, world", b"Goodbye, world"];
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 6, Challenge 45");
+
+	for &generator in &[TamperedGenerator::Zero, TamperedGenerator::PPlusOne] {
+		let results = forge(generator);
+		println!("{:?}: {:?}", generator, results);
+	}
+}
+
+/// Swaps g in a fresh key's parameters, forges one signature and checks it
+/// against each message.
+fn forge(generator: TamperedGenerator) -> Vec<bool> {
+	let mut public = PrivateKey::generate(&Parameters::challenge()).public_key();
+	public.params = generator.apply(&public.params);
+	let signature = magic_signature(&public, generator);
+	println!("{:?}: r = {}, s = {}", generator, signature.r, signature.s);
+	MESSAGES.iter().map(|message| trusting_verify(&public, HashAlgorithm::Sha1, message, &signature)).collect()
+}
+
+#[cfg(test)]
+mod set6challenge45 {
+	use cryptopalslib::attacks::dsa_generator::TamperedGenerator;
+
+	#[test]
+	fn zero_generator() {
+		assert_eq!(super::forge(TamperedGenerator::Zero), vec!(true, true));
+	}
+
+	#[test]
+	fn p_plus_one_generator() {
+		assert_eq!(super::forge(TamperedGenerator::PPlusOne), vec!(true, true));
+	}
+}
@@ -0,0 +1,92 @@
+// RSA parity oracle
+// When does this ever happen?
+// This is a bit of a toy problem, but it's very helpful for understanding
+// what RSA is doing (and also for why pure number-theoretic encryption is
+// terrifying). Trust us, you want to do this before trying the next
+// challenge. Also, it's fun.
+
+// Generate a 1024 bit RSA key pair.
+
+// Write an oracle function that uses the private key to answer the question
+// "is the plaintext of this message even or odd" (is the last bit of the
+// message 0 or 1). Imagine for instance a server that accepted
+// RSA-encrypted messages and checked the parity of their decryption to
+// validate them, and spat out an error if they were of the wrong parity.
+
+// Anyways: function returning true or false based on whether the decrypted
+// plaintext was even or odd, and nothing else.
+
+// Take the following string and un-Base64 it in your code (without looking
+// at it!) and encrypt it to the public key, creating a ciphertext:
+
+// VGhhdCdzIHdoeSBJIGZvdW5kIHlvdSBkb24ndCBwbGF5IGFyb3VuZCB3aXRoIHRoZSBGdW5reSBDb2xkIE1lZGluYQ==
+
+// With your oracle function, you can trivially decrypt the message.
+
+// Here's why:
+
+// RSA ciphertexts are just numbers. You can do trivial math on them. You
+// can for instance multiply a ciphertext by the RSA-encryption of another
+// number; the corresponding plaintext will be the product of those two
+// numbers.
+// If you double a ciphertext (multiply it by (2**e)%n), the resulting
+// plaintext will (obviously) be either even or odd.
+// If the plaintext after doubling is even, doubling the plaintext didn't
+// wrap the modulus --- the modulus is a prime number. That means the
+// plaintext is less than half the modulus.
+// You can repeatedly apply this heuristic, once per bit of the message,
+// checking your oracle function each time.
+
+// Your decryption function starts with bounds for the plaintext of [0,n].
+
+// Each iteration of the decryption cuts the bounds in half; either the
+// upper bound is reduced by half, or the lower bound is.
+
+// After log2(n) iterations, you have the decryption of the message.
+
+// Print the upper bound of the message as a string at each iteration;
+// you'll see the message decrypt "hollywood style".
+
+// Decrypt the string (after encoding it to a number) to verify that the
+// algorithm works.
+
+extern crate cryptopalslib;
+
+use cryptopalslib::attacks::rsa_parity::ParityOracle;
+use cryptopalslib::bignum::BigUint;
+use cryptopalslib::rsa::PrivateKey;
+
+static SECRET: &'static str = "VGhhdCdzIHdoeSBJIGZvdW5kIHlvdSBkb24ndCBwbGF5IGFyb3VuZCB3aXRoIHRoZSBGdW5reSBDb2xkIE1lZGluYQ==";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 6, Challenge 46");
+
+	let output = decrypt_secret(|bound| println!("{:?}", String::from_utf8_lossy(&bound.to_bytes_be())));
+	println!("decrypted: {:?}", String::from_utf8_lossy(&output));
+}
+
+fn secret() -> Vec<u8> {
+	cryptopalslib::convert::hex_string_to_decimal_pairs(&cryptopalslib::convert::base64_to_hex(SECRET))
+}
+
+/// Encrypts the secret under a fresh key, then decrypts it with only the
+/// parity oracle, passing each upper bound to `progress`.
+fn decrypt_secret<F: FnMut(&BigUint)>(progress: F) -> Vec<u8> {
+	let oracle = ParityOracle::new(PrivateKey::generate(1024, &BigUint::from(65537)));
+	let public = oracle.public_key();
+	let c = public.encrypt_raw(&BigUint::from_bytes_be(&secret()));
+	cryptopalslib::attacks::rsa_parity::recover(&public, &c, |x| oracle.is_even(x), progress).to_bytes_be()
+}
+
+#[cfg(test)]
+mod set6challenge46 {
+
+	#[test]
+	fn decrypt_secret() {
+		let mut iterations = 0;
+		let output = super::decrypt_secret(|_| iterations += 1);
+		assert_eq!(output, super::secret());
+		assert_eq!(iterations, 1024);
+	}
+}
@@ -0,0 +1,114 @@
+// Bleichenbacher's PKCS 1.5 Padding Oracle (Complete Case)
+// Cryptocurrency.
+// This is a continuation of challenge #47; it implements the complete BB'98
+// attack.
+
+// Set yourself up the way you did in #47, but this time generate a 768 bit
+// modulus.
+
+// To make the attack work with a realistic RSA keypair, you need to
+// reproduce step 2b from the paper, and your implementation of Step 3 needs
+// to handle multiple ranges.
+
+// The full Bleichenbacher attack works basically like this:
+
+// Starting from the smallest 's' that could possibly produce a plaintext
+// bigger than 2B, iteratively search for an 's' that produces a conformant
+// plaintext.
+// For our known 's1' and 'n', solve m1=m0s1-rn (again: just a definitional
+// thing) for possible values of 'r'.
+// This gives you a range of m1 values for which the oracle can return true.
+// Use this range to narrow the range of m0 values.
+// Use 'r' to compute a new 's', and repeat.
+
+// The "new s" part doesn't really work like that, but you get the idea.
+
+// This is going to take some time to get right, but you can reassure
+// yourself that it's working when you see ranges collapse.
+
+extern crate cryptopalslib;
+
+#[cfg(not(test))]
+use cryptopalslib::attacks::rsa_padding_oracle::{Attack, PaddingOracle};
+use cryptopalslib::bignum::BigUint;
+#[cfg(not(test))]
+use cryptopalslib::rsa::PrivateKey;
+
+static MESSAGE: &'static [u8] = b"kick it, CC";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 6, Challenge 48");
+
+	let oracle = PaddingOracle::new(PrivateKey::generate(768, &BigUint::from(3)));
+	let public = oracle.public_key();
+	let c = BigUint::from_bytes_be(&public.encrypt(MESSAGE));
+
+	// run the steps by hand, to watch the ranges collapse
+	let mut attack = Attack::new(&public, &c, |x| oracle.is_conforming(x));
+	attack.search_start();
+	println!("step 2.a: s = {} after {} queries", attack.s, attack.queries.step_2a);
+	loop {
+		if !attack.narrow() {
+			println!("no ranges left, so the oracle is inconsistent");
+			break;
+		}
+		println!("{} range(s), widest {} bits", attack.intervals.len(), widest(&attack.intervals));
+		if let Some(m) = attack.solution() {
+			let block = m.to_bytes_be_padded(public.size());
+			println!("decrypted: {:?}", cryptopalslib::rsa::unpad_encryption(&block).map(|x| String::from_utf8_lossy(&x).into_owned()));
+			break;
+		}
+		match attack.intervals.len() {
+			1 => attack.search_one_interval(),
+			_ => attack.search_linear(),
+		}
+	}
+	println!("queries: {:?}, {} in total", attack.queries, attack.queries.total());
+}
+
+/// The number of bits in the size of the widest range.
+fn widest(intervals: &[(BigUint, BigUint)]) -> usize {
+	intervals.iter().map(|(a, b)| (b - a).bits()).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod set6challenge48 {
+	use cryptopalslib::attacks::rsa_padding_oracle::{recover, PaddingOracle};
+	use cryptopalslib::bignum::BigUint;
+	use cryptopalslib::rsa::PrivateKey;
+
+	#[test]
+	fn oracle() {
+		let oracle = PaddingOracle::new(PrivateKey::generate(768, &BigUint::from(3)));
+		let public = oracle.public_key();
+		let c = BigUint::from_bytes_be(&public.encrypt(super::MESSAGE));
+		assert!(oracle.is_conforming(&c));
+
+		// doubling the plaintext moves it out of [2B, 3B)
+		let doubled = &(&c * &public.encrypt_raw(&BigUint::from(2))) % &public.n;
+		assert!(!oracle.is_conforming(&doubled));
+	}
+
+	// The whole attack takes tens of thousands of queries, which is too slow
+	// for a debug build at this size; run with --release -- --ignored.
+	#[test]
+	#[ignore]
+	fn recover_message() {
+		let key = PrivateKey::generate(768, &BigUint::from(3));
+		let oracle = PaddingOracle::new(key.clone());
+		let public = oracle.public_key();
+		let c = BigUint::from_bytes_be(&public.encrypt(super::MESSAGE));
+
+		let recovery = recover(&public, &c, |x| oracle.is_conforming(x)).unwrap();
+		assert_eq!(recovery.plaintext, key.decrypt_raw(&c));
+		let block = recovery.plaintext.to_bytes_be_padded(public.size());
+		assert_eq!(cryptopalslib::rsa::unpad_encryption(&block), Ok(super::MESSAGE.to_vec()));
+	}
+
+	#[test]
+	fn widest() {
+		let intervals = vec!((BigUint::from(10), BigUint::from(12)), (BigUint::from(20), BigUint::from(300)));
+		assert_eq!(super::widest(&intervals), 9);
+	}
+}
@@ -0,0 +1,143 @@
+// CBC-MAC Message Forgery
+// Let's talk about CBC-MAC.
+
+// CBC-MAC is like this:
+
+// Take the plaintext P.
+// Encrypt P under CBC with key K, yielding ciphertext C.
+// Chuck all of C but the last block C[n].
+// C[n] is the MAC.
+// Suppose there's an online banking application, and it carries out user
+// requests by talking to an API server over the network. Each request looks
+// like this:
+
+// message || IV || MAC
+
+// The message looks like this:
+
+// from=#{from_id}&to=#{to_id}&amount=#{amount}
+
+// Now, write an API server and a web frontend for it. (NOTE: No need to get
+// ambitious and write actual servers and web apps. Totally fine to go lo-fi
+// on this one.) The client and server should share a secret key K to sign
+// and verify messages.
+
+// The API server should accept messages, verify signatures, and carry out
+// each transaction if the MAC is valid. It's also publicly exposed - the
+// attacker can submit messages freely assuming he can forge the right MAC.
+
+// The web client should allow the attacker to generate valid messages for
+// accounts he controls. (Feel free to sanity check params here, but it's
+// not required.)
+
+// Now: you control the IV. Can you generate a message under your control
+// that gives you the same MAC as one you don't control? Forge a message
+// that will transfer 1M spacebucks from a target victim's account into your
+// account.
+
+// Now let's tune up that protocol a little bit.
+
+// As we now know, you're supposed to use a fixed IV with CBC-MAC, so let's
+// do that. We'll set ours at 0 for simplicity. This means the IV comes out
+// of the protocol:
+
+// message || MAC
+
+// Pretty simple, but we'll also adjust the message. For the purposes of
+// efficiency, the bank wants to be able to process multiple transactions in
+// a single request. So the message now looks like this:
+
+// from=#{from_id}&tx_list=#{transactions}
+
+// With the transaction list formatted like:
+
+// to:amount(;to:amount)*
+
+// There's still a weakness here: the MAC is vulnerable to length extension
+// attacks. How?
+
+// Well, the output of CBC-MAC is a valid IV for a new message.
+
+// "But we don't control the IV anymore!"
+
+// With sufficient mastery of CBC, we can fake it.
+
+// Your mission: capture a valid message from your target user. Use length
+// extension to add a transaction paying the attacker's account 1M
+// spacebucks.
+
+// Hint!
+// This would be a lot easier if you had full control over the first block
+// of your message, huh? Maybe you can simulate that.
+
+extern crate openssl;
+extern crate cryptopalslib;
+
+use cryptopalslib::attacks::cbc_mac::{Transfer, append_transfers, forge_sender, sign_transfer, sign_transfers, verify_transfer, verify_transfers};
+
+static VICTIM: &'static str = "1001";
+static ATTACKER: &'static str = "1002";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 7, Challenge 49");
+
+	let key = openssl::crypto::rand::rand_bytes(16);
+	println!("first version: {:?}", forge_single(&key));
+	println!("second version: {:?}", forge_list(&key));
+}
+
+/// The web frontend for the first version of the API. It only signs
+/// requests from the attacker's own account.
+fn frontend(key: &[u8], from: &str, to: &str, amount: u64) -> Option<Vec<u8>> {
+	match from == ATTACKER {
+		true => Some(sign_transfer(key, from, to, amount)),
+		false => None,
+	}
+}
+
+/// Has the frontend sign a transfer from the attacker to themselves, then
+/// rewrites the sender to the victim and submits it to the API server.
+fn forge_single(key: &[u8]) -> Option<Transfer> {
+	assert!(frontend(key, VICTIM, ATTACKER, 1000000).is_none());
+	let request = frontend(key, ATTACKER, ATTACKER, 1000000)?;
+	let forged = forge_sender(&request, VICTIM)?;
+	verify_transfer(key, &forged).ok()
+}
+
+/// Captures a list of transfers signed for the victim, and extends it with
+/// a payment to the attacker, using a request the attacker signed for
+/// themselves. Returns what the API server would carry out.
+fn forge_list(key: &[u8]) -> Option<Vec<Transfer>> {
+	// the whole first block of the attacker's request is lost, so it starts
+	// with a throwaway transfer
+	let own = sign_transfers(key, ATTACKER, &[(ATTACKER, 1), (ATTACKER, 1000000)]);
+
+	// what that block turns into only depends on the captured MAC, so if it
+	// would cut the list short, wait for the victim's next request
+	let forged = (700..1000).filter_map(|amount| {
+		let captured = sign_transfers(key, VICTIM, &[("1003", 25), ("1004", amount)]);
+		append_transfers(&captured, &own)
+	}).next()?;
+	verify_transfers(key, &forged).ok()
+}
+
+#[cfg(test)]
+mod set7challenge49 {
+	use cryptopalslib::attacks::cbc_mac::Transfer;
+
+	#[test]
+	fn forge_single() {
+		let key = openssl::crypto::rand::rand_bytes(16);
+		let transfer = super::forge_single(&key).unwrap();
+		assert_eq!(transfer, Transfer { from: super::VICTIM.to_string(), to: super::ATTACKER.to_string(), amount: 1000000 });
+	}
+
+	#[test]
+	fn forge_list() {
+		let key = openssl::crypto::rand::rand_bytes(16);
+		let transfers = super::forge_list(&key).unwrap();
+		assert_eq!(transfers[0], Transfer { from: super::VICTIM.to_string(), to: "1003".to_string(), amount: 25 });
+		assert_eq!(transfers.last(), Some(&Transfer { from: super::VICTIM.to_string(), to: super::ATTACKER.to_string(), amount: 1000000 }));
+	}
+}
@@ -0,0 +1,77 @@
+// Hashing with CBC-MAC
+// Sometimes people try to use CBC-MAC as a hash function.
+
+// This is a bad idea. Matt Green explains:
+
+// To make a long story short: cryptographic hash functions are public
+// functions (i.e., no secret key) that have the property of
+// collision-resistance (it's hard to find two messages with the same hash).
+// MACs are keyed functions that (typically) provide message unforgeability
+// -- a very different property. Moreover, they guarantee this only when the
+// key is secret.
+
+// Let's try a simple exercise.
+
+// Hash functions are often used for code verification. This snippet of
+// JavaScript (with newline):
+
+// alert('MZA who was that?');
+
+// Hashes to 296b8d7cb78a243dda4d0a61d33bbdd1 under CBC-MAC with a key of
+// "YELLOW SUBMARINE" and a 0 IV.
+
+// Forge a valid snippet of JavaScript that alerts "Ayo, the Wu is back!"
+// and hashes to the same value. Ensure that it runs in a browser.
+
+// Extra Credit
+// Write JavaScript code that downloads your file, checks its CBC-MAC, and
+// inserts it into the DOM iff it matches the expected hash.
+
+extern crate cryptopalslib;
+
+static KEY: &'static [u8] = b"YELLOW SUBMARINE";
+static TARGET: &'static [u8] = b"alert('MZA who was that?');\n";
+static PREFIX: &'static [u8] = b"alert('Ayo, the Wu is back!');//";
+
+#[cfg(not(test))]
+fn main() {
+	println!("Set 7, Challenge 50");
+
+	let snippet = forge_snippet();
+	println!("forged: {:?}", String::from_utf8_lossy(&snippet));
+	println!("hash: {}", cryptopalslib::convert::decimals_to_hex_string(cryptopalslib::mac::cbc_mac(KEY, &[0; 16], &snippet)));
+}
+
+/// Forges a snippet that alerts the new message and has the same CBC-MAC
+/// as the target. Everything after the alert is in a line comment, so the
+/// padding and the junk block mustn't contain a line break. Filler is added
+/// to the comment until they don't.
+fn forge_snippet() -> Vec<u8> {
+	let mut prefix = PREFIX.to_vec();
+	loop {
+		let forged = cryptopalslib::attacks::cbc_mac::collide(KEY, TARGET, &prefix);
+		let junk = &forged[PREFIX.len()..forged.len() - (TARGET.len() - 16)];
+		if !junk.iter().any(|&x| x == b'\n' || x == b'\r') {
+			return forged;
+		}
+		prefix.push(b'x');
+	}
+}
+
+#[cfg(test)]
+mod set7challenge50 {
+
+	#[test]
+	fn target_hash() {
+		let hash = cryptopalslib::mac::cbc_mac(super::KEY, &[0; 16], super::TARGET);
+		assert_eq!(cryptopalslib::convert::decimals_to_hex_string(hash), "296b8d7cb78a243dda4d0a61d33bbdd1");
+	}
+
+	#[test]
+	fn forge_snippet() {
+		let snippet = super::forge_snippet();
+		assert!(snippet.starts_with(super::PREFIX));
+		assert!(snippet.ends_with(&super::TARGET[16..]));
+		assert_eq!(cryptopalslib::mac::cbc_mac(super::KEY, &[0; 16], &snippet), cryptopalslib::mac::cbc_mac(super::KEY, &[0; 16], super::TARGET));
+	}
+}